@@ -4,11 +4,191 @@
 //! during initialization. This avoids system call overhead during
 //! hot proof computation paths.
 
-use crate::bump::BumpAlloc;
-use crate::config::{POLY_ARENA_SIZE, SCRATCH_ARENA_SIZE, WITNESS_ARENA_SIZE};
+use crate::bump::{BumpAlloc, BumpError, Marker};
+use crate::config::{
+    ARENA_ADDRESS_HINT_BASE, ARENA_ADDRESS_HINT_STRIDE, POLY_ARENA_SIZE, POLY_SIZE_ENV,
+    SCRATCH_ARENA_SIZE, SCRATCH_SIZE_ENV, WITNESS_ARENA_SIZE, WITNESS_SIZE_ENV,
+};
+use crate::platform::NumaPolicy;
 use crate::sys;
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
+/// Configuration for an [`ArenaManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaConfig {
+    /// Size of the witness arena in bytes.
+    pub witness_size: usize,
+    /// Size of the polynomial arena in bytes.
+    pub poly_size: usize,
+    /// Size of the scratch arena in bytes.
+    pub scratch_size: usize,
+    /// Back the polynomial arena with explicit huge pages of this size
+    /// (e.g. [`HUGE_PAGE_2MB`](crate::config::HUGE_PAGE_2MB)). The arena size is rounded up to a multiple
+    /// of it. Supported on Linux and on Windows (large pages, which need
+    /// `SeLockMemoryPrivilege`). If the huge-page mapping fails, normal pages are used instead
+    /// and the fallback is reported in [`ArenaStats`].
+    pub poly_huge_pages: Option<usize>,
+    /// Request transparent huge pages for the polynomial arena
+    /// (see [`BumpAlloc::advise_huge`]). Ignored if the kernel refuses.
+    pub poly_transparent_huge_pages: bool,
+    /// Number of per-thread shards the scratch arena is split into.
+    /// Each shard gets an equal, page-aligned slice of `scratch_size`.
+    pub scratch_shards: usize,
+    /// Return physical pages to the OS whenever an arena is reset.
+    /// Keeps RSS flat across proofs at the cost of re-faulting pages.
+    pub release_on_reset: bool,
+    /// Lock the witness arena into physical memory at creation, so witness
+    /// data never reaches swap. Creation fails if the lock limit is exceeded.
+    pub lock_witness: bool,
+    /// Over-reserve the witness arena by this many pages and start it at a
+    /// random page offset within the reservation, drawn from the OS CSPRNG.
+    /// Makes the witness address unpredictable even relative to the other
+    /// arenas, as defense in depth against heap grooming. Creation fails if
+    /// no CSPRNG is available. `0` disables it.
+    pub witness_random_offset_pages: usize,
+    /// Touch every arena page at creation (see [`ArenaManager::prefault`]),
+    /// trading a higher startup RSS for no page faults while proving.
+    pub prefault: bool,
+    /// NUMA placement of every arena's pages. If the kernel rejects it the
+    /// arenas keep the default policy and [`ArenaStats::numa_fallback`]
+    /// is set.
+    pub numa: Option<NumaPolicy>,
+    /// NUMA placement of the polynomial arena, overriding `numa`, e.g. to
+    /// interleave large vectors over every socket.
+    pub poly_numa: Option<NumaPolicy>,
+    /// Align the polynomial arena's base to this power of two, e.g.
+    /// [`HUGE_PAGE_2MB`](crate::config::HUGE_PAGE_2MB) for DMA buffers.
+    ///
+    /// The region is over-reserved by up to `poly_base_align` bytes of
+    /// address space to get there; no physical memory is spent. An
+    /// allocation at the start of the arena then needs no padding to reach
+    /// this alignment (see [`BumpAlloc::base_alignment`]). `0` keeps the
+    /// page alignment every arena has. Ignored when the arena is backed by
+    /// explicit huge pages, which are aligned to their own size.
+    pub poly_base_align: usize,
+    /// Ask the OS to map each arena at a fixed address derived from its
+    /// kind ([`ARENA_ADDRESS_HINT_BASE`] plus a multiple of
+    /// [`ARENA_ADDRESS_HINT_STRIDE`]), so layouts repeat across runs.
+    ///
+    /// Best effort: the hints never replace existing mappings, and an arena
+    /// whose address is taken is placed wherever the OS chooses. Huge-page
+    /// polynomial arenas, and a second manager, take whatever they get.
+    pub stable_addresses: bool,
+    /// Chain extra chunks onto an exhausted arena instead of failing.
+    /// Chunks are freed on reset; only the initial regions keep stable
+    /// addresses.
+    #[cfg(feature = "growable")]
+    pub growable: bool,
+}
+
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        Self::compiled()
+    }
+}
+
+impl ArenaConfig {
+    /// The compiled-in defaults, ignoring the environment.
+    ///
+    /// Same as [`Default::default`], but usable in `const` context.
+    pub const fn compiled() -> Self {
+        Self {
+            witness_size: WITNESS_ARENA_SIZE,
+            poly_size: POLY_ARENA_SIZE,
+            scratch_size: SCRATCH_ARENA_SIZE,
+            poly_huge_pages: None,
+            poly_transparent_huge_pages: false,
+            scratch_shards: 1,
+            release_on_reset: false,
+            lock_witness: false,
+            witness_random_offset_pages: 0,
+            prefault: false,
+            numa: None,
+            poly_numa: None,
+            poly_base_align: 0,
+            stable_addresses: false,
+            #[cfg(feature = "growable")]
+            growable: false,
+        }
+    }
+
+    /// The default configuration, with arena sizes overridden by the
+    /// [`WITNESS_SIZE_ENV`], [`POLY_SIZE_ENV`] and [`SCRATCH_SIZE_ENV`]
+    /// environment variables.
+    ///
+    /// Values are byte counts with an optional `K`, `M`, `G` or `T` suffix
+    /// (powers of 1024), e.g. `512M`. Unset variables keep the compiled
+    /// default; invalid ones are reported once on stderr and ignored.
+    /// Reading the environment does not allocate, so this is safe during
+    /// global allocator initialization.
+    pub fn from_env() -> Self {
+        static WARNED: [AtomicBool; 3] = [const { AtomicBool::new(false) }; 3];
+
+        Self {
+            witness_size: env_size(WITNESS_SIZE_ENV, WITNESS_ARENA_SIZE, &WARNED[0]),
+            poly_size: env_size(POLY_SIZE_ENV, POLY_ARENA_SIZE, &WARNED[1]),
+            scratch_size: env_size(SCRATCH_SIZE_ENV, SCRATCH_ARENA_SIZE, &WARNED[2]),
+            ..Self::default()
+        }
+    }
+}
+
+/// Read a size from environment variable `name`, falling back to `default`.
+fn env_size(name: &CStr, default: usize, warned: &AtomicBool) -> usize {
+    let mut buf = [0u8; 64];
+    let Some(value) = sys::env_var(name, &mut buf) else {
+        return default;
+    };
+    match parse_size(value) {
+        Some(size) => size,
+        None => {
+            // `eprintln!` may allocate, re-entering the allocator this
+            // configures; write the pieces out raw instead.
+            if !warned.swap(true, Ordering::Relaxed) {
+                for part in [
+                    b"[nalloc] ignoring invalid ".as_slice(),
+                    name.to_bytes(),
+                    b"=",
+                    value,
+                    b", using the default size\n",
+                ] {
+                    sys::write_stderr(part);
+                }
+            }
+            default
+        }
+    }
+}
+
+/// Parse a non-zero byte count with an optional binary `K`/`M`/`G`/`T` suffix.
+fn parse_size(value: &[u8]) -> Option<usize> {
+    let value = std::str::from_utf8(value).ok()?.trim();
+    let (digits, shift) = match value.as_bytes().last()?.to_ascii_uppercase() {
+        b'K' => (&value[..value.len() - 1], 10),
+        b'M' => (&value[..value.len() - 1], 20),
+        b'G' => (&value[..value.len() - 1], 30),
+        b'T' => (&value[..value.len() - 1], 40),
+        _ => (value, 0),
+    };
+    let size = digits
+        .parse::<usize>()
+        .ok()?
+        .checked_mul(1usize.checked_shl(shift)?)?;
+    (size > 0).then_some(size)
+}
+
+/// The cursors of every arena at one point in time, taken by
+/// [`ArenaManager::snapshot`].
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    witness: Marker,
+    polynomial: Marker,
+    scratch: Box<[Marker]>,
+}
+
 /// Manages multiple specialized memory arenas.
 ///
 /// Each arena is optimized for a specific purpose:
@@ -18,7 +198,24 @@ use std::sync::Arc;
 pub struct ArenaManager {
     witness: Arc<BumpAlloc>,
     polynomial: Arc<BumpAlloc>,
-    scratch: Arc<BumpAlloc>,
+    /// Scratch shards; threads are assigned to shards round-robin.
+    scratch: Box<[Arc<BumpAlloc>]>,
+    /// Bytes between the start of the witness reservation and the arena.
+    witness_offset: usize,
+    /// Total size of the witness reservation.
+    witness_reserved: usize,
+    /// Bytes between the start of the polynomial reservation and the arena.
+    poly_offset: usize,
+    /// Total size of the polynomial reservation.
+    poly_reserved: usize,
+    /// Total size of the scratch reservation backing all shards.
+    scratch_reserved: usize,
+    /// The polynomial arena is backed by explicit huge pages.
+    huge_pages: bool,
+    /// Huge pages were requested for the polynomial arena but unavailable.
+    huge_page_fallback: bool,
+    /// A NUMA policy was requested but the kernel rejected it.
+    numa_fallback: bool,
 }
 
 impl ArenaManager {
@@ -27,15 +224,22 @@ impl ArenaManager {
     /// This will allocate a total of ~1.4 GB of virtual memory.
     /// Note: On modern OSes, virtual memory is cheap; physical pages
     /// are only allocated when touched.
+    ///
+    /// The sizes can be overridden through environment variables; see
+    /// [`ArenaConfig::from_env`].
     pub fn new() -> Result<Self, crate::platform::AllocFailed> {
-        let witness_ptr = sys::alloc(WITNESS_ARENA_SIZE)?;
-        let poly_ptr = sys::alloc(POLY_ARENA_SIZE)?;
-        let scratch_ptr = sys::alloc(SCRATCH_ARENA_SIZE)?;
-
-        Ok(Self {
-            witness: Arc::new(unsafe { BumpAlloc::new(witness_ptr, WITNESS_ARENA_SIZE) }),
-            polynomial: Arc::new(unsafe { BumpAlloc::new(poly_ptr, POLY_ARENA_SIZE) }),
-            scratch: Arc::new(unsafe { BumpAlloc::new(scratch_ptr, SCRATCH_ARENA_SIZE) }),
+        Self::with_config(ArenaConfig::from_env())
+    }
+
+    /// Create a new ArenaManager with every arena bound to NUMA node `node`.
+    ///
+    /// Pair this with pinning the proving threads to that node's CPUs. Where
+    /// NUMA placement is unavailable the arenas are created anyway, and
+    /// [`ArenaStats::numa_fallback`] reports it.
+    pub fn new_on_node(node: u32) -> Result<Self, crate::platform::AllocFailed> {
+        Self::with_config(ArenaConfig {
+            numa: Some(NumaPolicy::Bind(node)),
+            ..ArenaConfig::from_env()
         })
     }
 
@@ -47,17 +251,158 @@ impl ArenaManager {
         poly_size: usize,
         scratch_size: usize,
     ) -> Result<Self, crate::platform::AllocFailed> {
-        let witness_ptr = sys::alloc(witness_size)?;
-        let poly_ptr = sys::alloc(poly_size)?;
-        let scratch_ptr = sys::alloc(scratch_size)?;
-
-        Ok(Self {
-            witness: Arc::new(unsafe { BumpAlloc::new(witness_ptr, witness_size) }),
-            polynomial: Arc::new(unsafe { BumpAlloc::new(poly_ptr, poly_size) }),
-            scratch: Arc::new(unsafe { BumpAlloc::new(scratch_ptr, scratch_size) }),
+        Self::with_config(ArenaConfig {
+            witness_size,
+            poly_size,
+            scratch_size,
+            ..ArenaConfig::default()
+        })
+    }
+
+    /// Create a new ArenaManager with default sizes and a sharded scratch arena.
+    ///
+    /// Each thread allocates from its own shard, so concurrent scratch
+    /// allocations don't contend on a single atomic cursor.
+    pub fn with_scratch_shards(shards: usize) -> Result<Self, crate::platform::AllocFailed> {
+        Self::with_config(ArenaConfig {
+            scratch_shards: shards,
+            ..ArenaConfig::default()
         })
     }
 
+    /// Create a new ArenaManager from a full configuration.
+    pub fn with_config(config: ArenaConfig) -> Result<Self, crate::platform::AllocFailed> {
+        let page = sys::page_size();
+        let shards = config.scratch_shards.max(1);
+        let shard_size = if shards == 1 {
+            config.scratch_size
+        } else {
+            (config.scratch_size / shards) & !(page - 1)
+        };
+        // Too many shards for the scratch size would leave them empty.
+        if shard_size == 0 {
+            return Err(crate::platform::AllocFailed::new(config.scratch_size));
+        }
+        let witness_reserved = config
+            .witness_random_offset_pages
+            .checked_mul(page)
+            .and_then(|offset| offset.checked_add(config.witness_size))
+            .ok_or(crate::platform::AllocFailed::new(config.witness_size))?;
+        let witness_offset = match config.witness_random_offset_pages {
+            0 => 0,
+            pages => {
+                let mut seed = [0u8; 8];
+                if !sys::fill_random(&mut seed) {
+                    return Err(crate::platform::AllocFailed::new(witness_reserved));
+                }
+                (u64::from_ne_bytes(seed) % (pages as u64 + 1)) as usize * page
+            }
+        };
+        let hint = |kind| match config.stable_addresses {
+            true => address_hint(kind),
+            false => std::ptr::null_mut(),
+        };
+        let witness_ptr = reserve(hint(ArenaKind::Witness), witness_reserved)?;
+        let witness_ptr = unsafe { witness_ptr.add(witness_offset) };
+
+        let mut poly_size = config.poly_size;
+        let mut huge_pages = false;
+        let mut huge_page_fallback = false;
+        let poly_ptr = match config.poly_huge_pages {
+            Some(page_size) => {
+                let huge_size = poly_size
+                    .checked_next_multiple_of(page_size)
+                    .ok_or(crate::platform::AllocFailed::new(poly_size))?;
+                match sys::alloc_huge_pages(huge_size, page_size) {
+                    Ok(ptr) => {
+                        poly_size = huge_size;
+                        huge_pages = true;
+                        Some(ptr)
+                    }
+                    Err(_) => {
+                        huge_page_fallback = true;
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        let (poly_ptr, poly_offset, poly_reserved) = match poly_ptr {
+            Some(ptr) => (ptr, 0, poly_size),
+            None => reserve_aligned(
+                hint(ArenaKind::Polynomial),
+                poly_size,
+                config.poly_base_align,
+                page,
+            )?,
+        };
+
+        let scratch_ptr = reserve(hint(ArenaKind::Scratch), config.scratch_size)?;
+        let scratch = (0..shards)
+            .map(|i| {
+                Arc::new(unsafe {
+                    BumpAlloc::new_zeroed(scratch_ptr.add(i * shard_size), shard_size)
+                        .with_label("scratch")
+                })
+            })
+            .collect();
+
+        let mut numa_fallback = false;
+        let mut bind = |ptr: *mut u8, size: usize, policy: Option<NumaPolicy>| {
+            if let Some(policy) = policy {
+                numa_fallback |= sys::bind_numa(ptr, size, policy).is_err();
+            }
+        };
+        bind(witness_ptr, config.witness_size, config.numa);
+        bind(poly_ptr, poly_size, config.poly_numa.or(config.numa));
+        bind(scratch_ptr, config.scratch_size, config.numa);
+
+        let manager = Self {
+            witness: Arc::new(unsafe {
+                BumpAlloc::new_zeroed(witness_ptr, config.witness_size).with_label("witness")
+            }),
+            polynomial: Arc::new(unsafe {
+                BumpAlloc::new_zeroed(poly_ptr, poly_size).with_label("polynomial")
+            }),
+            scratch,
+            witness_offset,
+            witness_reserved,
+            poly_offset,
+            poly_reserved,
+            scratch_reserved: config.scratch_size,
+            huge_pages,
+            huge_page_fallback,
+            numa_fallback,
+        };
+
+        #[cfg(all(not(feature = "guard-pages"), target_os = "windows"))]
+        manager.for_each_arena(|arena| {
+            // The huge-page mapping is committed up front.
+            if !(manager.huge_pages && std::ptr::eq(arena, &*manager.polynomial)) {
+                unsafe { arena.set_commit_on_demand() };
+            }
+        });
+        if config.poly_transparent_huge_pages && !manager.huge_pages {
+            let _ = manager.polynomial.advise_huge();
+        }
+        if config.release_on_reset {
+            manager.for_each_arena(|arena| arena.set_release_on_reset(true));
+        }
+        #[cfg(feature = "growable")]
+        if config.growable {
+            manager.for_each_arena(|arena| arena.set_growable(true));
+        }
+        if config.lock_witness {
+            manager.witness.lock_pages()?;
+        }
+        if config.prefault {
+            // Nothing else can see the arenas yet.
+            unsafe { manager.prefault() };
+        }
+
+        Ok(manager)
+    }
+
     /// Get a handle to the witness arena.
     #[inline]
     pub fn witness(&self) -> Arc<BumpAlloc> {
@@ -71,9 +416,168 @@ impl ArenaManager {
     }
 
     /// Get a handle to the scratch arena.
+    ///
+    /// With a sharded scratch arena, this is the calling thread's shard.
     #[inline]
     pub fn scratch(&self) -> Arc<BumpAlloc> {
-        self.scratch.clone()
+        if self.scratch.len() == 1 {
+            return self.scratch[0].clone();
+        }
+        self.scratch[thread_shard_index() % self.scratch.len()].clone()
+    }
+
+    /// Number of shards the scratch arena is split into.
+    #[inline]
+    pub fn scratch_shard_count(&self) -> usize {
+        self.scratch.len()
+    }
+
+    /// Apply `f` to every arena, including each scratch shard.
+    #[inline]
+    fn for_each_arena(&self, mut f: impl FnMut(&BumpAlloc)) {
+        f(&self.witness);
+        f(&self.polynomial);
+        for shard in self.scratch.iter() {
+            f(shard);
+        }
+    }
+
+    /// Sum a per-arena quantity across all scratch shards.
+    #[inline]
+    fn scratch_sum(&self, f: impl Fn(&BumpAlloc) -> usize) -> usize {
+        self.scratch.iter().map(|shard| f(shard)).sum()
+    }
+
+    /// Fault in every free page of every arena.
+    ///
+    /// Call once before proving starts so the first touch of each page does
+    /// not land in a hot loop. See [`BumpAlloc::prefault`].
+    ///
+    /// # Safety
+    /// No allocation may run concurrently with this call.
+    pub unsafe fn prefault(&self) {
+        self.for_each_arena(|arena| arena.prefault());
+    }
+
+    /// Successful allocations per size class, summed over all arenas.
+    ///
+    /// See [`BumpAlloc::size_histogram`] for the class boundaries.
+    #[cfg(feature = "stats-histogram")]
+    pub fn size_histogram(&self) -> [usize; crate::config::SIZE_CLASSES] {
+        let mut total = [0; crate::config::SIZE_CLASSES];
+        self.for_each_arena(|arena| {
+            for (sum, count) in total.iter_mut().zip(arena.size_histogram()) {
+                *sum += count;
+            }
+        });
+        total
+    }
+
+    /// Restart the statistics of every arena; see [`BumpAlloc::reset_stats`].
+    pub fn reset_stats(&self) {
+        self.for_each_arena(BumpAlloc::reset_stats);
+    }
+
+    /// Install (or with `None`, remove) an exhaustion callback on every arena.
+    ///
+    /// See [`ExhaustionHook`](crate::ExhaustionHook) for what it may do.
+    pub fn set_exhaustion_hook(&self, hook: Option<crate::ExhaustionHook>) {
+        self.for_each_arena(|arena| arena.set_exhaustion_hook(hook));
+    }
+
+    /// Identify which arena `ptr` points into, if any.
+    ///
+    /// Returns `None` for pointers from outside the arenas, e.g. memory
+    /// from the system allocator.
+    #[inline]
+    pub fn arena_of(&self, ptr: *const u8) -> Option<ArenaKind> {
+        if self.scratch.iter().any(|shard| shard.contains(ptr)) {
+            Some(ArenaKind::Scratch)
+        } else if self.polynomial.contains(ptr) {
+            Some(ArenaKind::Polynomial)
+        } else if self.witness.contains(ptr) {
+            Some(ArenaKind::Witness)
+        } else {
+            None
+        }
+    }
+
+    /// Find the arena, or scratch shard, that `ptr` points into.
+    #[inline]
+    pub(crate) fn owner_of(&self, ptr: *const u8) -> Option<&BumpAlloc> {
+        self.scratch
+            .iter()
+            .map(|shard| &**shard)
+            .chain([&*self.polynomial, &*self.witness])
+            .find(|arena| arena.contains(ptr))
+    }
+
+    /// Get the `[base, limit)` addresses of every arena, one entry per
+    /// scratch shard; see [`BumpAlloc::region`].
+    ///
+    /// Guard pages are not part of any region.
+    pub fn regions(&self) -> Vec<(ArenaKind, (usize, usize))> {
+        let mut regions = vec![
+            (ArenaKind::Witness, self.witness.region()),
+            (ArenaKind::Polynomial, self.polynomial.region()),
+        ];
+        regions.extend(
+            self.scratch
+                .iter()
+                .map(|shard| (ArenaKind::Scratch, shard.region())),
+        );
+        regions
+    }
+
+    /// Check whether `ptr` points into any of the arenas.
+    #[inline]
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        self.arena_of(ptr).is_some()
+    }
+
+    /// Capture the cursors of all arenas at once.
+    ///
+    /// Pass the result to [`restore`](Self::restore) to throw away
+    /// everything allocated since, e.g. after an abandoned speculative proof.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            witness: self.witness.checkpoint(),
+            polynomial: self.polynomial.checkpoint(),
+            scratch: self
+                .scratch
+                .iter()
+                .map(|shard| shard.checkpoint())
+                .collect(),
+        }
+    }
+
+    /// Rewind every arena to `snapshot`.
+    ///
+    /// All arenas are checked before any is rewound, so on error nothing has
+    /// changed. Fails with [`BumpError::StaleMarker`] if an arena was reset
+    /// since the snapshot and with [`BumpError::MarkerAhead`] if it is
+    /// behind it.
+    ///
+    /// # Safety
+    /// All memory allocated after the snapshot becomes invalid, and no
+    /// allocation may run concurrently with this call.
+    pub unsafe fn restore(&self, snapshot: Snapshot) -> Result<(), BumpError> {
+        debug_assert_eq!(snapshot.scratch.len(), self.scratch.len());
+        if snapshot.scratch.len() != self.scratch.len() {
+            return Err(BumpError::StaleMarker);
+        }
+        self.witness.check_marker(snapshot.witness)?;
+        self.polynomial.check_marker(snapshot.polynomial)?;
+        for (shard, &marker) in self.scratch.iter().zip(snapshot.scratch.iter()) {
+            shard.check_marker(marker)?;
+        }
+
+        self.witness.restore(snapshot.witness)?;
+        self.polynomial.restore(snapshot.polynomial)?;
+        for (shard, &marker) in self.scratch.iter().zip(snapshot.scratch.iter()) {
+            shard.restore(marker)?;
+        }
+        Ok(())
     }
 
     /// Reset all arenas.
@@ -83,9 +587,35 @@ impl ArenaManager {
     /// # Safety
     /// This will invalidate all memory previously allocated from these arenas.
     pub unsafe fn reset_all(&self) {
+        self.reset_witness();
+        self.reset_polynomial();
+        self.reset_scratch();
+    }
+
+    /// Securely wipe and reset only the witness arena.
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the witness arena.
+    pub unsafe fn reset_witness(&self) {
         self.witness.secure_reset();
+    }
+
+    /// Reset only the polynomial arena.
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the polynomial arena.
+    pub unsafe fn reset_polynomial(&self) {
         self.polynomial.reset();
-        self.scratch.reset();
+    }
+
+    /// Reset only the scratch arena (every shard).
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the scratch arena.
+    pub unsafe fn reset_scratch(&self) {
+        for shard in self.scratch.iter() {
+            shard.reset();
+        }
     }
 
     /// Get statistics about arena usage.
@@ -95,14 +625,146 @@ impl ArenaManager {
             witness_capacity: self.witness.capacity(),
             polynomial_used: self.polynomial.used(),
             polynomial_capacity: self.polynomial.capacity(),
-            scratch_used: self.scratch.used(),
-            scratch_capacity: self.scratch.capacity(),
+            scratch_used: self.scratch_sum(BumpAlloc::used),
+            scratch_capacity: self.scratch_sum(BumpAlloc::capacity),
+            witness_peak: self.witness.peak_used(),
+            polynomial_peak: self.polynomial.peak_used(),
+            scratch_peak: self.scratch_sum(BumpAlloc::peak_used),
+            witness_allocs: self.witness.alloc_count(),
+            polynomial_allocs: self.polynomial.alloc_count(),
+            scratch_allocs: self.scratch_sum(BumpAlloc::alloc_count),
+            witness_alloc_failures: self.witness.alloc_fail_count(),
+            polynomial_alloc_failures: self.polynomial.alloc_fail_count(),
+            scratch_alloc_failures: self.scratch_sum(BumpAlloc::alloc_fail_count),
+            witness_cas_retries: self.witness.cas_retries(),
+            polynomial_cas_retries: self.polynomial.cas_retries(),
+            scratch_cas_retries: self.scratch_sum(BumpAlloc::cas_retries),
+            alignment_waste_bytes: self.witness.alignment_waste()
+                + self.polynomial.alignment_waste()
+                + self.scratch_sum(BumpAlloc::alignment_waste),
+            polynomial_color_waste_bytes: self.polynomial.color_waste(),
+            polynomial_huge_page_fallback: self.huge_page_fallback,
+            numa_fallback: self.numa_fallback,
+            system_fallbacks: 0,
         }
     }
 }
 
+/// Address [`ArenaConfig::stable_addresses`] requests for `kind`, or null
+/// if it does not fit the address space.
+fn address_hint(kind: ArenaKind) -> *mut u8 {
+    let index = match kind {
+        ArenaKind::Witness => 0,
+        ArenaKind::Polynomial => 1,
+        ArenaKind::Scratch => 2,
+    };
+    usize::try_from(ARENA_ADDRESS_HINT_BASE + index * ARENA_ADDRESS_HINT_STRIDE)
+        .map_or(std::ptr::null_mut(), |addr| addr as *mut u8)
+}
+
+/// Reserve the memory region backing one arena, preferably at `hint` (see
+/// [`sys::alloc_at`]; null for no preference).
+///
+/// With the `guard-pages` feature, the region is surrounded by inaccessible
+/// pages so that overruns fault at the offending access.
+#[inline]
+fn reserve(hint: *mut u8, size: usize) -> Result<*mut u8, crate::platform::AllocFailed> {
+    #[cfg(feature = "guard-pages")]
+    {
+        sys::alloc_guarded_at(hint, size)
+    }
+    // Windows commits eagerly, so reserve only and let the arena commit
+    // as its cursor advances (Linux gets the same effect from NORESERVE).
+    #[cfg(all(not(feature = "guard-pages"), target_os = "windows"))]
+    {
+        sys::reserve_at(hint, size)
+    }
+    #[cfg(all(not(feature = "guard-pages"), not(target_os = "windows")))]
+    {
+        sys::alloc_at(hint, size)
+    }
+}
+
+/// Reserve a region for a `size`-byte arena whose base is aligned to
+/// `align`, beyond the `page` alignment [`reserve`] already gives.
+///
+/// Returns the arena base, its offset into the reservation, and the size of
+/// the reservation to hand back to [`release`].
+fn reserve_aligned(
+    hint: *mut u8,
+    size: usize,
+    align: usize,
+    page: usize,
+) -> Result<(*mut u8, usize, usize), crate::platform::AllocFailed> {
+    debug_assert!(align == 0 || align.is_power_of_two());
+    if align <= page {
+        return Ok((reserve(hint, size)?, 0, size));
+    }
+    let reserved = size
+        .checked_add(align - page)
+        .ok_or(crate::platform::AllocFailed::new(size))?;
+    let raw = reserve(hint, reserved)?;
+    let offset = raw.align_offset(align);
+    Ok((unsafe { raw.add(offset) }, offset, reserved))
+}
+
+/// Release a region obtained from [`reserve`].
+#[inline]
+fn release(ptr: *mut u8, size: usize) -> Result<(), crate::platform::AllocFailed> {
+    #[cfg(feature = "guard-pages")]
+    {
+        sys::dealloc_guarded(ptr, size)
+    }
+    #[cfg(not(feature = "guard-pages"))]
+    {
+        sys::dealloc(ptr, size)
+    }
+}
+
+/// Stable per-thread index used to pick a scratch shard.
+///
+/// Threads are numbered in the order they first allocate. The thread-local
+/// is const-initialized and has no destructor, so accessing it never
+/// allocates (important when running as the global allocator).
+#[inline]
+fn thread_shard_index() -> usize {
+    static NEXT_INDEX: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static INDEX: Cell<usize> = const { Cell::new(usize::MAX) };
+    }
+
+    INDEX
+        .try_with(|index| {
+            if index.get() == usize::MAX {
+                index.set(NEXT_INDEX.fetch_add(1, Ordering::Relaxed));
+            }
+            index.get()
+        })
+        .unwrap_or(0)
+}
+
+/// The arenas managed by an [`ArenaManager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArenaKind {
+    /// The witness arena, for private inputs.
+    Witness,
+    /// The polynomial arena, for FFT/NTT vectors.
+    Polynomial,
+    /// The scratch arena (any shard), for temporary buffers.
+    Scratch,
+}
+
+/// Callback invoked when an arena's utilization crosses a threshold.
+///
+/// Receives the arena and its utilization at the time. Like
+/// [`ExhaustionHook`](crate::ExhaustionHook), it runs inside an allocation,
+/// so it must not allocate through [`NAlloc`](crate::NAlloc) if that is the
+/// global allocator.
+pub type PressureCallback = fn(arena: ArenaKind, utilization: f64);
+
 /// Statistics about arena memory usage.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ArenaStats {
     pub witness_used: usize,
     pub witness_capacity: usize,
@@ -110,6 +772,49 @@ pub struct ArenaStats {
     pub polynomial_capacity: usize,
     pub scratch_used: usize,
     pub scratch_capacity: usize,
+    /// Highest witness usage observed, preserved across resets.
+    pub witness_peak: usize,
+    /// Highest polynomial usage observed, preserved across resets.
+    pub polynomial_peak: usize,
+    /// Highest scratch usage observed, preserved across resets.
+    pub scratch_peak: usize,
+    /// Successful witness allocations, preserved across resets.
+    pub witness_allocs: usize,
+    /// Successful polynomial allocations, preserved across resets.
+    pub polynomial_allocs: usize,
+    /// Successful scratch allocations, preserved across resets.
+    pub scratch_allocs: usize,
+    /// Witness allocations that failed due to exhaustion.
+    pub witness_alloc_failures: usize,
+    /// Polynomial allocations that failed due to exhaustion.
+    pub polynomial_alloc_failures: usize,
+    /// Scratch allocations that failed due to exhaustion.
+    pub scratch_alloc_failures: usize,
+    /// Witness allocations retried after losing the cursor to another
+    /// thread; see [`BumpAlloc::cas_retries`].
+    pub witness_cas_retries: usize,
+    /// Polynomial allocations retried after losing the cursor to another
+    /// thread.
+    pub polynomial_cas_retries: usize,
+    /// Scratch allocations retried after losing the cursor to another
+    /// thread. A high ratio to `scratch_allocs` calls for more shards.
+    pub scratch_cas_retries: usize,
+    /// Bytes skipped to align allocations, across all arenas, preserved
+    /// across resets.
+    pub alignment_waste_bytes: usize,
+    /// Part of the polynomial alignment waste spent placing cache-colored
+    /// allocations, preserved across resets.
+    pub polynomial_color_waste_bytes: usize,
+    /// Huge pages were requested for the polynomial arena but the
+    /// normal page path had to be used instead.
+    pub polynomial_huge_page_fallback: bool,
+    /// A NUMA policy was requested but could not be applied, so the arenas
+    /// use the default placement.
+    pub numa_fallback: bool,
+    /// Allocations [`NAlloc`](crate::NAlloc) served from the system
+    /// allocator because the chosen arena was exhausted. Always zero when
+    /// taken directly from an [`ArenaManager`].
+    pub system_fallbacks: usize,
 }
 
 impl ArenaStats {
@@ -122,6 +827,66 @@ impl ArenaStats {
     pub fn total_capacity(&self) -> usize {
         self.witness_capacity + self.polynomial_capacity + self.scratch_capacity
     }
+
+    /// Total successful allocations across all arenas.
+    pub fn total_allocs(&self) -> usize {
+        self.witness_allocs + self.polynomial_allocs + self.scratch_allocs
+    }
+
+    /// Emit these statistics through the `metrics` facade.
+    ///
+    /// Per-arena sizes become `nalloc_arena_{used,capacity,peak}_bytes`
+    /// gauges and allocation counts become `nalloc_arena_allocs_total`,
+    /// `nalloc_arena_alloc_failures_total` and `nalloc_arena_cas_retries_total`
+    /// counters, all labelled with
+    /// `arena`. System fallbacks are counted in `nalloc_system_fallbacks_total`
+    /// and alignment padding in `nalloc_alignment_waste_bytes_total`.
+    #[cfg(feature = "metrics")]
+    pub fn export_metrics(&self) {
+        let arenas = [
+            (
+                "witness",
+                self.witness_used,
+                self.witness_capacity,
+                self.witness_peak,
+                self.witness_allocs,
+                self.witness_alloc_failures,
+                self.witness_cas_retries,
+            ),
+            (
+                "polynomial",
+                self.polynomial_used,
+                self.polynomial_capacity,
+                self.polynomial_peak,
+                self.polynomial_allocs,
+                self.polynomial_alloc_failures,
+                self.polynomial_cas_retries,
+            ),
+            (
+                "scratch",
+                self.scratch_used,
+                self.scratch_capacity,
+                self.scratch_peak,
+                self.scratch_allocs,
+                self.scratch_alloc_failures,
+                self.scratch_cas_retries,
+            ),
+        ];
+        for (arena, used, capacity, peak, allocs, failures, retries) in arenas {
+            metrics::gauge!("nalloc_arena_used_bytes", "arena" => arena).set(used as f64);
+            metrics::gauge!("nalloc_arena_capacity_bytes", "arena" => arena).set(capacity as f64);
+            metrics::gauge!("nalloc_arena_peak_bytes", "arena" => arena).set(peak as f64);
+            metrics::counter!("nalloc_arena_allocs_total", "arena" => arena)
+                .absolute(allocs as u64);
+            metrics::counter!("nalloc_arena_alloc_failures_total", "arena" => arena)
+                .absolute(failures as u64);
+            metrics::counter!("nalloc_arena_cas_retries_total", "arena" => arena)
+                .absolute(retries as u64);
+        }
+        metrics::counter!("nalloc_system_fallbacks_total").absolute(self.system_fallbacks as u64);
+        metrics::counter!("nalloc_alignment_waste_bytes_total")
+            .absolute(self.alignment_waste_bytes as u64);
+    }
 }
 
 impl Drop for ArenaManager {
@@ -130,24 +895,40 @@ impl Drop for ArenaManager {
         // Note: For global allocator usage, this rarely runs (program exit).
         // But for library usage, proper cleanup is essential.
 
-        let witness_ptr = self.witness.base_ptr();
-        let poly_ptr = self.polynomial.base_ptr();
-        let scratch_ptr = self.scratch.base_ptr();
+        // Secrets must be wiped explicitly; releasing the pages is not enough.
+        #[cfg(debug_assertions)]
+        self.witness.assert_wiped("dropping the arena manager");
+
+        // Locked pages must be unlocked before the region is released.
+        let _ = self.witness.unlock_pages();
+        let _ = self.polynomial.unlock_pages();
+        for shard in self.scratch.iter() {
+            let _ = shard.unlock_pages();
+        }
+
+        let witness_ptr = self.witness.base_ptr().wrapping_sub(self.witness_offset);
+        let poly_ptr = self.polynomial.base_ptr().wrapping_sub(self.poly_offset);
+        let scratch_ptr = self.scratch[0].base_ptr();
 
-        let witness_size = self.witness.capacity();
-        let poly_size = self.polynomial.capacity();
-        let scratch_size = self.scratch.capacity();
+        let witness_size = self.witness_reserved;
+        let poly_size = self.poly_reserved;
+        let scratch_size = self.scratch_reserved;
 
         // Best-effort deallocation - ignore errors on shutdown
-        let _ = sys::dealloc(witness_ptr, witness_size);
-        let _ = sys::dealloc(poly_ptr, poly_size);
-        let _ = sys::dealloc(scratch_ptr, scratch_size);
+        let _ = release(witness_ptr, witness_size);
+        if self.huge_pages {
+            let _ = sys::dealloc(poly_ptr, poly_size);
+        } else {
+            let _ = release(poly_ptr, poly_size);
+        }
+        let _ = release(scratch_ptr, scratch_size);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::HUGE_PAGE_2MB;
 
     #[test]
     fn test_arena_manager_creation() {
@@ -161,6 +942,20 @@ mod tests {
         assert_eq!(stats.total_used(), 0);
     }
 
+    #[test]
+    fn test_default_sizes_fit_address_space() {
+        // Scaled down on 32-bit targets, where the 64-bit defaults would
+        // take most of the address space.
+        let total = WITNESS_ARENA_SIZE
+            .checked_add(POLY_ARENA_SIZE)
+            .and_then(|sum| sum.checked_add(SCRATCH_ARENA_SIZE))
+            .unwrap();
+        assert!(total <= usize::MAX / 16);
+
+        let manager = ArenaManager::with_config(ArenaConfig::compiled()).unwrap();
+        assert_eq!(manager.stats().total_capacity(), total);
+    }
+
     #[test]
     fn test_arena_stats() {
         let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
@@ -176,6 +971,499 @@ mod tests {
         assert!(stats.scratch_used >= 512);
     }
 
+    #[test]
+    fn test_huge_page_config_falls_back() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 3 * 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            poly_huge_pages: Some(HUGE_PAGE_2MB),
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        let stats = manager.stats();
+        if stats.polynomial_huge_page_fallback {
+            assert_eq!(stats.polynomial_capacity, 3 * 1024 * 1024);
+        } else {
+            // Rounded up to whole huge pages
+            assert_eq!(stats.polynomial_capacity, 4 * 1024 * 1024);
+        }
+
+        let ptr = manager.polynomial().alloc(1024 * 1024, 64);
+        assert!(!ptr.is_null());
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 1024 * 1024) };
+    }
+
+    #[test]
+    fn test_numa_binding() {
+        let config = ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            ..ArenaConfig::default()
+        };
+
+        // Node 0 exists wherever NUMA is supported at all.
+        let manager = ArenaManager::with_config(ArenaConfig {
+            numa: Some(NumaPolicy::Bind(0)),
+            ..config
+        })
+        .unwrap();
+        let ptr = manager.polynomial().alloc(4096, 64);
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 4096) };
+
+        let manager = ArenaManager::with_config(ArenaConfig {
+            numa: Some(NumaPolicy::Bind(4096)),
+            ..config
+        })
+        .unwrap();
+        assert!(manager.stats().numa_fallback);
+        assert!(
+            !ArenaManager::with_config(config)
+                .unwrap()
+                .stats()
+                .numa_fallback
+        );
+    }
+
+    #[test]
+    fn test_transparent_huge_pages() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 4 * 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            poly_transparent_huge_pages: true,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        // Only a hint: allocation works whether or not THP is enabled.
+        let ptr = manager.polynomial().alloc(4 * 1024 * 1024, 64);
+        assert!(!ptr.is_null());
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 4 * 1024 * 1024) };
+    }
+
+    #[test]
+    fn test_scratch_shards() {
+        use std::thread;
+
+        let manager = Arc::new(
+            ArenaManager::with_config(ArenaConfig {
+                witness_size: 1024 * 1024,
+                poly_size: 1024 * 1024,
+                scratch_size: 4 * 1024 * 1024,
+                scratch_shards: 4,
+                ..ArenaConfig::default()
+            })
+            .unwrap(),
+        );
+        assert_eq!(manager.scratch_shard_count(), 4);
+        assert_eq!(manager.stats().scratch_capacity, 4 * 1024 * 1024);
+
+        // Shards smaller than a page are refused, not created empty.
+        assert!(ArenaManager::with_config(ArenaConfig {
+            scratch_size: 64 * 1024,
+            scratch_shards: 32,
+            ..ArenaConfig::default()
+        })
+        .is_err());
+
+        let bases: Vec<usize> = (0..4)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                thread::spawn(move || {
+                    let shard = manager.scratch();
+                    // A thread keeps using the same shard
+                    assert!(Arc::ptr_eq(&shard, &manager.scratch()));
+                    assert!(!shard.alloc(1024, 8).is_null());
+                    shard.base_ptr() as usize
+                })
+            })
+            .map(|h| h.join().unwrap())
+            .collect();
+
+        // Four fresh threads land on four distinct shards
+        let mut distinct = bases.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 4);
+
+        // Stats aggregate across shards
+        assert_eq!(manager.stats().scratch_used, 4 * 1024);
+    }
+
+    #[cfg(all(feature = "guard-pages", unix))]
+    #[test]
+    fn test_guard_page_faults_on_overrun() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let scratch = manager.scratch();
+        let end = unsafe { scratch.base_ptr().add(scratch.capacity()) };
+
+        unsafe {
+            let pid = libc::fork();
+            assert!(pid >= 0, "fork failed");
+            if pid == 0 {
+                // Child: write one byte past the arena, which must fault.
+                std::ptr::write_volatile(end, 0x42);
+                libc::_exit(0);
+            }
+
+            let mut status = 0;
+            libc::waitpid(pid, &mut status, 0);
+            assert!(libc::WIFSIGNALED(status), "overrun did not fault");
+            assert_eq!(libc::WTERMSIG(status), libc::SIGSEGV);
+        }
+    }
+
+    #[test]
+    fn test_release_on_reset() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            release_on_reset: true,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        let ptr = manager.polynomial().alloc(512 * 1024, 4096);
+        unsafe {
+            std::ptr::write_bytes(ptr, 0xAB, 512 * 1024);
+            manager.reset_all();
+        }
+
+        // The arena remains usable after its pages were released
+        let ptr = manager.polynomial().alloc(512 * 1024, 4096);
+        assert!(!ptr.is_null());
+        unsafe { std::ptr::write_bytes(ptr, 0xCD, 512 * 1024) };
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_trim_to() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = manager.polynomial();
+        let page = sys::page_size();
+
+        let ptr = poly.alloc(512 * 1024, page);
+        unsafe {
+            poly.reset();
+            // Stale data left past the cursor.
+            std::ptr::write_bytes(ptr, 0xAB, 512 * 1024);
+            poly.trim_to(64 * 1024).unwrap();
+        }
+        // The kept prefix stays resident with its contents.
+        assert!(unsafe { std::slice::from_raw_parts(ptr, 64 * 1024) }
+            .iter()
+            .all(|&b| b == 0xAB));
+        // Dropped pages read back as zero on Linux.
+        #[cfg(target_os = "linux")]
+        assert!(
+            unsafe { std::slice::from_raw_parts(ptr.add(64 * 1024), 448 * 1024) }
+                .iter()
+                .all(|&b| b == 0)
+        );
+
+        // Live allocations are never trimmed.
+        let live = poly.alloc(128 * 1024, page);
+        unsafe {
+            std::ptr::write_bytes(live, 0xCD, 128 * 1024);
+            poly.trim_to(0).unwrap();
+        }
+        assert!(unsafe { std::slice::from_raw_parts(live, 128 * 1024) }
+            .iter()
+            .all(|&b| b == 0xCD));
+    }
+
+    #[test]
+    fn test_peak_stats_survive_reset() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+
+        let _ = manager.witness().alloc(4096, 8);
+        let _ = manager.polynomial().alloc(8192, 64);
+        let _ = manager.scratch().alloc(1024, 8);
+        unsafe { manager.reset_all() };
+
+        let stats = manager.stats();
+        assert_eq!(stats.total_used(), 0);
+        assert!(stats.witness_peak >= 4096);
+        assert!(stats.polynomial_peak >= 8192);
+        assert!(stats.scratch_peak >= 1024);
+    }
+
+    #[test]
+    fn test_alloc_count_stats() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+
+        let _ = manager.witness().alloc(64, 8);
+        let _ = manager.polynomial().alloc(64, 8);
+        let _ = manager.scratch().alloc(64, 8);
+        let _ = manager.scratch().alloc(64, 8);
+        assert!(manager.scratch().alloc(4 * 1024 * 1024, 8).is_null());
+
+        let stats = manager.stats();
+        assert_eq!(stats.witness_allocs, 1);
+        assert_eq!(stats.polynomial_allocs, 1);
+        assert_eq!(stats.scratch_allocs, 2);
+        assert_eq!(stats.scratch_alloc_failures, 1);
+        assert_eq!(stats.total_allocs(), 4);
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_polynomial_arena() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            growable: true,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        let ptr = manager.polynomial().alloc(4 * 1024 * 1024, 64);
+        assert!(!ptr.is_null());
+        assert!(manager.stats().polynomial_capacity > 4 * 1024 * 1024);
+
+        unsafe { manager.reset_all() };
+        assert_eq!(manager.stats().polynomial_capacity, 1024 * 1024);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_stats_serde_roundtrip() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let _ = manager.witness().alloc(4096, 8);
+
+        let stats = manager.stats();
+        let json = serde_json::to_value(stats).unwrap();
+        assert_eq!(json["witness_used"], 4096);
+        assert_eq!(json["witness_capacity"], 1024 * 1024);
+
+        let back: ArenaStats = serde_json::from_value(json).unwrap();
+        assert_eq!(back.witness_used, stats.witness_used);
+        assert_eq!(back.total_capacity(), stats.total_capacity());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_export_metrics() {
+        use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let _ = manager.witness().alloc(4096, 8);
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || manager.stats().export_metrics());
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let lookup = |name: &str, arena: &str| {
+            snapshot
+                .iter()
+                .find(|(key, _, _, _)| {
+                    key.key().name() == name
+                        && key
+                            .key()
+                            .labels()
+                            .any(|l| l.key() == "arena" && l.value() == arena)
+                })
+                .map(|(_, _, _, value)| value)
+        };
+        assert_eq!(
+            lookup("nalloc_arena_used_bytes", "witness"),
+            Some(&DebugValue::Gauge(4096.0.into()))
+        );
+        assert_eq!(
+            lookup("nalloc_arena_allocs_total", "witness"),
+            Some(&DebugValue::Counter(1))
+        );
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size(b"4096"), Some(4096));
+        assert_eq!(parse_size(b"64k"), Some(64 * 1024));
+        assert_eq!(parse_size(b"512M"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_size(b" 2G "), Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_size(b""), None);
+        assert_eq!(parse_size(b"0"), None);
+        assert_eq!(parse_size(b"M"), None);
+        assert_eq!(parse_size(b"12X"), None);
+        assert_eq!(parse_size(b"-1G"), None);
+        assert_eq!(parse_size(b"99999999999999999999T"), None);
+    }
+
+    #[test]
+    fn test_prefault_keeps_memory_zeroed() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 64 * 1024,
+            poly_size: 64 * 1024,
+            scratch_size: 64 * 1024,
+            prefault: true,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        let witness = manager.witness();
+        let ptr = witness.alloc(64 * 1024, 8);
+        assert!(!ptr.is_null());
+        let data = unsafe { std::slice::from_raw_parts(ptr, 64 * 1024) };
+        assert!(data.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_granular_resets() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = manager.witness().alloc(64, 8);
+        unsafe { witness.write_bytes(0xAA, 64) };
+        manager.polynomial().alloc(128, 8);
+        manager.scratch().alloc(256, 8);
+
+        unsafe { manager.reset_scratch() };
+        let stats = manager.stats();
+        assert_eq!(stats.scratch_used, 0);
+        assert_eq!(stats.polynomial_used, 128);
+        assert_eq!(stats.witness_used, 64);
+
+        unsafe { manager.reset_polynomial() };
+        assert_eq!(manager.stats().polynomial_used, 0);
+        assert_eq!(manager.stats().witness_used, 64);
+
+        unsafe { manager.reset_witness() };
+        assert_eq!(manager.stats().witness_used, 0);
+        assert_eq!(unsafe { *witness }, 0);
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let _ = manager.witness().alloc(128, 8);
+        let _ = manager.polynomial().alloc(256, 64);
+
+        let snapshot = manager.snapshot();
+        let _ = manager.witness().alloc(4096, 8);
+        let _ = manager.polynomial().alloc(4096, 64);
+        let _ = manager.scratch().alloc(4096, 8);
+
+        unsafe { manager.restore(snapshot.clone()).unwrap() };
+        let stats = manager.stats();
+        assert_eq!(stats.witness_used, 128);
+        assert_eq!(stats.polynomial_used, 256);
+        assert_eq!(stats.scratch_used, 0);
+
+        // A reset in between invalidates the snapshot, even once the
+        // arena has grown past the saved cursor again.
+        unsafe { manager.reset_polynomial() };
+        let _ = manager.polynomial().alloc(8192, 64);
+        let _ = manager.witness().alloc(4096, 8);
+        assert_eq!(
+            unsafe { manager.restore(snapshot) },
+            Err(BumpError::StaleMarker)
+        );
+        // Nothing was rewound.
+        assert_eq!(manager.stats().witness_used, 128 + 4096);
+    }
+
+    #[test]
+    fn test_stable_addresses() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            stable_addresses: true,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+        let _ = manager.polynomial().alloc(64, 8);
+
+        // The hints are only requests, but an empty stretch of a 64-bit
+        // Linux address space honors them (past the leading guard page).
+        #[cfg(all(target_os = "linux", target_pointer_width = "64"))]
+        for (kind, (base, _)) in manager.regions() {
+            let hint = address_hint(kind) as usize;
+            assert!((hint..hint + 2 * sys::page_size()).contains(&base));
+        }
+    }
+
+    #[test]
+    fn test_poly_base_align() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 64 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 64 * 1024,
+            poly_base_align: 4 * HUGE_PAGE_2MB,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+        let poly = manager.polynomial();
+        assert!(poly.base_alignment() >= 4 * HUGE_PAGE_2MB);
+        assert_eq!(manager.stats().polynomial_capacity, 1024 * 1024);
+
+        let ptr = poly.alloc(1024 * 1024, poly.max_align());
+        assert!(!ptr.is_null());
+        assert_eq!(poly.last_padding(), 0);
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 1024 * 1024) };
+    }
+
+    #[test]
+    fn test_witness_random_offset() {
+        let config = ArenaConfig {
+            witness_size: 64 * 1024,
+            poly_size: 64 * 1024,
+            scratch_size: 64 * 1024,
+            witness_random_offset_pages: 1024,
+            ..ArenaConfig::default()
+        };
+        let offsets: Vec<usize> = (0..4)
+            .map(|_| {
+                let manager = ArenaManager::with_config(config).unwrap();
+                assert_eq!(manager.stats().witness_capacity, 64 * 1024);
+                let ptr = manager.witness().alloc(64 * 1024, 8);
+                unsafe { std::ptr::write_bytes(ptr, 0xAB, 64 * 1024) };
+                assert_eq!(ptr as usize % sys::page_size(), 0);
+                manager.witness_offset
+            })
+            .collect();
+        assert!(offsets.iter().all(|&o| o <= 1024 * sys::page_size()));
+        // Four draws from 1025 slots are all equal with negligible odds.
+        assert!(offsets.iter().any(|&o| o != offsets[0]));
+    }
+
+    #[test]
+    fn test_arena_of() {
+        let manager = ArenaManager::with_config(ArenaConfig {
+            witness_size: 1024 * 1024,
+            poly_size: 1024 * 1024,
+            scratch_size: 1024 * 1024,
+            scratch_shards: 2,
+            ..ArenaConfig::default()
+        })
+        .unwrap();
+
+        let witness = manager.witness().alloc(64, 8);
+        let poly = manager.polynomial().alloc(64, 8);
+        let scratch = manager.scratch().alloc(64, 8);
+        assert_eq!(manager.arena_of(witness), Some(ArenaKind::Witness));
+        assert_eq!(manager.arena_of(poly), Some(ArenaKind::Polynomial));
+        assert_eq!(manager.arena_of(scratch), Some(ArenaKind::Scratch));
+
+        let regions = manager.regions();
+        assert_eq!(regions.len(), 4);
+        for (kind, (base, limit)) in regions {
+            assert_eq!(manager.arena_of(base as *const u8), Some(kind));
+            assert_eq!(manager.arena_of((limit - 1) as *const u8), Some(kind));
+        }
+        let (base, limit) = manager.witness().region();
+        assert!((base..limit).contains(&(witness as usize)));
+
+        let foreign = Box::new(0u8);
+        assert_eq!(manager.arena_of(&*foreign), None);
+        assert!(!manager.contains(&*foreign));
+    }
+
     #[test]
     fn test_drop_deallocates() {
         // This test verifies that Drop runs without panicking
@@ -0,0 +1,153 @@
+//! A single value in arena memory.
+//!
+//! [`ArenaBox`] is the one-object counterpart of the typed slice helpers:
+//! it moves a value into an arena and derefs to it.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+use crate::bump::BumpAlloc;
+
+/// An owned `T` stored in arena memory.
+///
+/// Unlike `Box`, dropping an `ArenaBox` frees nothing: the memory is only
+/// reclaimed when the arena is reset, and no destructor ever runs. `T` is
+/// therefore restricted to `Copy` types, which have no drop glue.
+///
+/// The box must not be used after its arena is reset; like every other
+/// arena allocation, its memory is handed out again. Debug builds check
+/// the arena's [`generation`](BumpAlloc::generation) on every deref and
+/// panic on such a use-after-reset.
+pub struct ArenaBox<'a, T: Copy> {
+    ptr: NonNull<T>,
+    /// Arena and generation the value was allocated in.
+    #[cfg(debug_assertions)]
+    origin: (&'a BumpAlloc, usize),
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: Copy> ArenaBox<'a, T> {
+    /// Move `value` into `arena`.
+    ///
+    /// Returns `None` if the arena is exhausted.
+    #[inline]
+    pub fn new_in(arena: &'a BumpAlloc, value: T) -> Option<Self> {
+        Self::new_in_aligned(arena, value, core::mem::align_of::<T>())
+    }
+
+    /// Move `value` into `arena` at an `align`-byte boundary, which must be
+    /// a power of two no smaller than `align_of::<T>()`.
+    #[inline]
+    pub(crate) fn new_in_aligned(arena: &'a BumpAlloc, value: T, align: usize) -> Option<Self> {
+        debug_assert!(align.is_power_of_two() && align >= core::mem::align_of::<T>());
+        let ptr = if core::mem::size_of::<T>() == 0 {
+            NonNull::dangling()
+        } else {
+            let raw = arena.alloc(core::mem::size_of::<T>(), align);
+            NonNull::new(raw as *mut T)?
+        };
+        unsafe { ptr.as_ptr().write(value) };
+        Some(Self {
+            ptr,
+            #[cfg(debug_assertions)]
+            origin: (arena, arena.generation()),
+            _marker: PhantomData,
+        })
+    }
+
+    #[inline(always)]
+    #[track_caller]
+    fn check_generation(&self) {
+        #[cfg(debug_assertions)]
+        assert_eq!(
+            self.origin.0.generation(),
+            self.origin.1,
+            "ArenaBox used after its arena was reset"
+        );
+    }
+
+    /// Turn the box into a plain reference with the arena's lifetime.
+    #[inline]
+    pub fn leak(this: Self) -> &'a mut T {
+        this.check_generation();
+        unsafe { &mut *this.ptr.as_ptr() }
+    }
+
+    /// Get a raw pointer to the value.
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *mut T {
+        this.ptr.as_ptr()
+    }
+}
+
+impl<T: Copy> Deref for ArenaBox<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.check_generation();
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: Copy> DerefMut for ArenaBox<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        self.check_generation();
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: Copy + fmt::Debug> fmt::Debug for ArenaBox<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+// Safety: an `ArenaBox` is a unique reference to its `T`.
+unsafe impl<T: Copy + Send> Send for ArenaBox<'_, T> {}
+unsafe impl<T: Copy + Sync> Sync for ArenaBox<'_, T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    #[test]
+    fn test_new_in() {
+        let mut buffer = vec![0u8; 1024];
+        let arena = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let mut value = ArenaBox::new_in(&arena, [7u64; 3]).unwrap();
+        value[1] = 9;
+        assert_eq!(*value, [7, 9, 7]);
+        assert!(arena.contains(ArenaBox::as_ptr(&value) as *const u8));
+        assert_eq!(ArenaBox::as_ptr(&value) as usize % 8, 0);
+
+        // Zero-sized values take no arena space.
+        let used = arena.used();
+        let _unit = ArenaBox::new_in(&arena, ()).unwrap();
+        assert_eq!(arena.used(), used);
+
+        assert!(ArenaBox::new_in(&arena, [0u8; 2048]).is_none());
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "std"))]
+    fn test_use_after_reset_panics() {
+        let mut buffer = vec![0u8; 1024];
+        let arena = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let value = ArenaBox::new_in(&arena, 5u32).unwrap();
+        assert_eq!(*value, 5);
+        assert_eq!(arena.generation(), 0);
+
+        unsafe { arena.reset() };
+        assert_eq!(arena.generation(), 1);
+        let stale = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| *value));
+        assert!(stale.is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Bucket Arena for nalloc.
+//!
+//! The `BucketArena` serves the accumulator buckets of Pippenger-style
+//! multi-scalar multiplication:
+//!
+//! - **Fixed stride**: Each window's buckets are one packed block.
+//! - **64-byte aligned**: Every block starts on a cache line.
+//! - **Rewind per window**: Resetting is a single cursor store.
+
+use crate::bump::{BumpAlloc, BumpError, Marker};
+use crate::config::CACHE_LINE_ALIGN;
+use std::sync::Arc;
+
+/// Specialized handle for MSM bucket accumulators.
+///
+/// Remembers the cursor of the underlying arena at creation;
+/// [`reset_buckets`](Self::reset_buckets) rewinds to it, discarding every
+/// bucket block allocated since. Nothing else should allocate from that
+/// arena in between, as it would be discarded too. Use
+/// [`NAlloc::buckets`](crate::NAlloc::buckets) for a handle over a private
+/// region.
+pub struct BucketArena {
+    inner: Arc<BumpAlloc>,
+    /// Cursor position to rewind to between windows.
+    start: Marker,
+    /// Bytes in use in `inner` at creation.
+    start_used: usize,
+}
+
+impl BucketArena {
+    /// Create a `BucketArena` starting at the current cursor of `inner`.
+    #[inline]
+    pub fn new(inner: Arc<BumpAlloc>) -> Self {
+        let start = inner.checkpoint();
+        let start_used = inner.used();
+        Self {
+            inner,
+            start,
+            start_used,
+        }
+    }
+
+    /// Allocate `count` buckets of `bucket_bytes` bytes each.
+    ///
+    /// The buckets are packed back to back behind a 64-byte aligned base.
+    /// Returns a null pointer if the arena is exhausted or the size
+    /// overflows.
+    #[inline]
+    pub fn alloc_buckets(&self, count: usize, bucket_bytes: usize) -> *mut u8 {
+        debug_assert!(count > 0);
+        debug_assert!(bucket_bytes > 0);
+        match count.checked_mul(bucket_bytes) {
+            Some(size) => self.inner.alloc(size, CACHE_LINE_ALIGN),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    /// Rewind to where the handle was created, e.g. before the next window.
+    ///
+    /// Fails with [`BumpError::StaleMarker`] if the underlying arena was
+    /// reset in the meantime. Blocks that spilled into chained chunks of a
+    /// growable arena are only reclaimed by a full reset; until then they
+    /// stay counted by [`used`](Self::used).
+    ///
+    /// # Safety
+    /// Every bucket block allocated from this handle becomes invalid.
+    #[inline]
+    pub unsafe fn reset_buckets(&self) -> Result<(), BumpError> {
+        self.inner.restore(self.start)
+    }
+
+    /// Get the number of bytes the buckets currently occupy, including
+    /// alignment padding.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.inner.used().saturating_sub(self.start_used)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NAlloc;
+
+    #[test]
+    fn test_buckets_rewind_per_window() {
+        let alloc = NAlloc::new();
+        let buckets = alloc.buckets(64 * 1024).unwrap();
+
+        // 2^8 - 1 buckets of three 32-byte coordinates.
+        let window = buckets.alloc_buckets(255, 96);
+        assert!(!window.is_null());
+        assert_eq!(window as usize % CACHE_LINE_ALIGN, 0);
+        assert_eq!(buckets.used(), 255 * 96);
+
+        unsafe { buckets.reset_buckets().unwrap() };
+        assert_eq!(buckets.used(), 0);
+        assert_eq!(buckets.alloc_buckets(255, 96), window);
+
+        assert!(buckets.alloc_buckets(usize::MAX, 2).is_null());
+        assert!(buckets.alloc_buckets(1024, 96).is_null());
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_used_counts_spilled_buckets() {
+        let mut buffer = vec![0u8; 1024];
+        let arena = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        arena.set_growable(true);
+        let buckets = BucketArena::new(Arc::new(arena));
+
+        assert!(!buckets.alloc_buckets(8, 96).is_null());
+        let in_region = buckets.used();
+        for _ in 0..8 {
+            assert!(!buckets.alloc_buckets(8, 96).is_null());
+        }
+        assert_eq!(buckets.used(), in_region + 8 * 8 * 96);
+
+        unsafe { buckets.reset_buckets().unwrap() };
+        assert_eq!(buckets.used(), 8 * 8 * 96);
+    }
+}
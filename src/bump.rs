@@ -4,10 +4,356 @@
 //! a pointer. This module provides a thread-safe, atomic bump allocator
 //! optimized for ZK prover workloads.
 
-use std::ptr::NonNull;
-use std::sync::atomic::{compiler_fence, AtomicBool, AtomicUsize, Ordering};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ptr::NonNull;
+use core::sync::atomic::{compiler_fence, AtomicBool, AtomicPtr, AtomicUsize, Ordering};
 
-use crate::config::SECURE_WIPE_PATTERN;
+#[cfg(all(feature = "std", target_os = "windows"))]
+use crate::config::COMMIT_CHUNK;
+#[cfg(not(feature = "std"))]
+use crate::config::PAGE_ALIGN;
+#[cfg(feature = "stats-histogram")]
+use crate::config::SIZE_CLASSES;
+use crate::config::{
+    CACHE_COLOR_SPAN, CACHE_COLOR_STRIDE, CACHE_LINE_ALIGN, EXHAUSTION_CONTEXT_TOP,
+    SECURE_WIPE_PATTERN,
+};
+#[cfg(feature = "std")]
+use crate::platform::{sys, AllocFailed, Protection};
+use crate::sync::{Mutex, MutexGuard};
+
+/// AddressSanitizer manual poisoning.
+///
+/// Free memory of every region is poisoned and each block unpoisoned as it
+/// is handed out, so ASan reports accesses that run from one block into
+/// the padding or free space after it.
+#[cfg(feature = "sanitizer")]
+mod asan {
+    use core::ffi::c_void;
+
+    extern "C" {
+        fn __asan_poison_memory_region(addr: *const c_void, size: usize);
+        fn __asan_unpoison_memory_region(addr: *const c_void, size: usize);
+    }
+
+    /// Make `[addr, addr+len)` inaccessible.
+    #[inline]
+    pub(super) fn poison(addr: usize, len: usize) {
+        unsafe { __asan_poison_memory_region(addr as *const c_void, len) }
+    }
+
+    /// Make `[addr, addr+len)` accessible again.
+    #[inline]
+    pub(super) fn unpoison(addr: usize, len: usize) {
+        unsafe { __asan_unpoison_memory_region(addr as *const c_void, len) }
+    }
+}
+
+/// A single allocation captured while record mode is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocRecord {
+    /// Offset of the allocation from the arena base.
+    pub offset: usize,
+    /// Size of the allocation in bytes.
+    pub size: usize,
+    /// Alignment the allocation was made with.
+    pub align: usize,
+    /// Bytes past an `align` boundary the allocation was placed at, as for
+    /// [`BumpAlloc::alloc_with_phase`]; 0 for a plain allocation.
+    pub phase: usize,
+}
+
+/// Snapshot of arena state taken when an allocation failed in record mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExhaustionContext {
+    /// Size of the request that failed.
+    pub requested_size: usize,
+    /// Alignment of the request that failed.
+    pub requested_align: usize,
+    /// Bytes remaining in the arena at the time of failure.
+    pub remaining: usize,
+    /// The largest live allocations at the time of failure, largest first.
+    pub largest: Vec<AllocRecord>,
+}
+
+/// Describes an allocation that was moved by [`BumpAlloc::reset_keeping_last`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Relocation {
+    /// Address of the allocation before the reset.
+    pub old: *mut u8,
+    /// Address of the allocation after the reset.
+    pub new: *mut u8,
+    /// Size of the allocation in bytes.
+    pub size: usize,
+}
+
+/// A saved cursor position, created by [`BumpAlloc::checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Marker {
+    cursor: usize,
+    /// Number of resets the arena had gone through when the marker was taken.
+    resets: usize,
+}
+
+/// RAII guard that rewinds a [`BumpAlloc`] to a checkpoint on drop.
+///
+/// Created by [`BumpAlloc::scope`]. Dereferences to the underlying
+/// allocator, so allocations can be made through the guard directly.
+/// The guard is `!Send`: it must be dropped on the thread that created it.
+pub struct ScopeGuard<'a> {
+    alloc: &'a BumpAlloc,
+    marker: Marker,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Deref for ScopeGuard<'_> {
+    type Target = BumpAlloc;
+
+    #[inline]
+    fn deref(&self) -> &BumpAlloc {
+        self.alloc
+    }
+}
+
+impl Drop for ScopeGuard<'_> {
+    fn drop(&mut self) {
+        // If the arena was reset inside the scope, there is nothing to reclaim.
+        let _ = unsafe { self.alloc.restore(self.marker) };
+    }
+}
+
+/// Exclusive handle allocating from a [`BumpAlloc`] without atomics.
+///
+/// Created by [`BumpAlloc::take_exclusive`]. The `&mut` borrow proves no
+/// other thread can allocate meanwhile, so the cursor is a plain `usize`
+/// and each allocation is a handful of arithmetic instructions instead of
+/// a compare-and-swap. The cursor and statistics are written back to the
+/// arena when the handle drops. The handle can be moved to another thread
+/// but not shared.
+pub struct ExclusiveBump<'a> {
+    alloc: &'a mut BumpAlloc,
+    cursor: usize,
+    /// Allocations and padding not yet added to the arena's statistics.
+    allocs: usize,
+    padding: usize,
+    last_padding: usize,
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+impl ExclusiveBump<'_> {
+    /// Allocate memory with the given size and alignment.
+    ///
+    /// Behaves like [`BumpAlloc::alloc`]. In record mode, and when the
+    /// region is exhausted, the request goes through the shared path.
+    #[inline(always)]
+    pub fn alloc(&mut self, size: usize, align: usize) -> *mut u8 {
+        debug_assert!(size > 0);
+        debug_assert!(align.is_power_of_two());
+
+        let alloc = &*self.alloc;
+        if align <= alloc.max_align && !alloc.is_recording() {
+            let phase = alloc.cursor_phase(size, align, 0);
+            if let Some((aligned, next)) = alloc.fit(self.cursor, size, align, phase) {
+                #[cfg(feature = "verify-invariants")]
+                alloc.verify_block(self.cursor, aligned, size, align, 0);
+                #[cfg(all(feature = "std", target_os = "windows"))]
+                if !alloc.commit_to(next) {
+                    return self.alloc_shared(size, align);
+                }
+                self.last_padding = aligned - self.cursor;
+                self.padding += self.last_padding;
+                self.allocs += 1;
+                self.cursor = next;
+                #[cfg(feature = "stats-histogram")]
+                alloc.histogram[size_class(size)].fetch_add(1, Ordering::Relaxed);
+                let ptr = alloc.mirror(aligned, size) as *mut u8;
+                #[cfg(feature = "sanitizer")]
+                asan::unpoison(ptr as usize, size);
+                #[cfg(feature = "tracing")]
+                alloc.trace_alloc(size, align, ptr);
+                return ptr;
+            }
+        }
+        self.alloc_shared(size, align)
+    }
+
+    /// Allocate zero-initialized memory, like [`BumpAlloc::alloc_zeroed`].
+    #[inline]
+    pub fn alloc_zeroed(&mut self, size: usize, align: usize) -> *mut u8 {
+        let ptr = self.alloc(size, align);
+        let alloc = &*self.alloc;
+        if !ptr.is_null()
+            && (alloc.is_recycled() || !alloc.fresh_zeroed)
+            && alloc.mirror(ptr as usize, size) + size > alloc.zeroed_end.load(Ordering::Acquire)
+        {
+            unsafe { core::ptr::write_bytes(ptr, 0, size) };
+        }
+        ptr
+    }
+
+    /// Hand a request the fast path cannot serve to the arena.
+    #[cold]
+    fn alloc_shared(&mut self, size: usize, align: usize) -> *mut u8 {
+        self.flush();
+        let ptr = self.alloc.alloc(size, align);
+        self.cursor = self.alloc.cursor.load(Ordering::Relaxed);
+        ptr
+    }
+
+    /// Write the cursor and pending statistics back to the arena.
+    fn flush(&mut self) {
+        let alloc = &*self.alloc;
+        alloc.cursor.store(self.cursor, Ordering::Relaxed);
+        alloc.peak.fetch_max(
+            self.cursor - alloc.base.as_ptr() as usize,
+            Ordering::Relaxed,
+        );
+        if self.allocs > 0 {
+            alloc.alloc_count.fetch_add(self.allocs, Ordering::Relaxed);
+            alloc.count_padding(self.last_padding);
+            alloc
+                .alignment_waste
+                .fetch_add(self.padding - self.last_padding, Ordering::Relaxed);
+        }
+        self.allocs = 0;
+        self.padding = 0;
+    }
+
+    /// Get the number of bytes currently allocated from the arena.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.cursor - self.alloc.base.as_ptr() as usize
+    }
+
+    /// Get the remaining capacity of the region in bytes.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.alloc.limit.as_ptr() as usize - self.cursor
+    }
+}
+
+impl Drop for ExclusiveBump<'_> {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// Callback invoked when an allocation fails because the arena is exhausted.
+///
+/// Receives the requested size and alignment and the bytes still remaining.
+/// It runs inside the failing allocation, so it must not allocate from the
+/// same arena (or from [`NAlloc`](crate::NAlloc) if that is the global
+/// allocator): the arena is already full and the call could recurse.
+pub type ExhaustionHook = fn(size: usize, align: usize, remaining: usize);
+
+/// Error type for fallible bump allocator operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpError {
+    /// The operation requires record mode, which is not enabled.
+    RecordingDisabled,
+    /// The marker lies ahead of the current cursor.
+    MarkerAhead,
+    /// The arena was reset after the marker was taken.
+    StaleMarker,
+    /// The operation is not supported by an arena growing downward.
+    GrowsDown,
+    /// The offset lies past the current cursor.
+    OffsetAhead,
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BumpError {}
+
+impl fmt::Display for BumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpError::RecordingDisabled => write!(f, "Record mode is not enabled"),
+            BumpError::MarkerAhead => write!(f, "Marker is ahead of the current cursor"),
+            BumpError::StaleMarker => write!(f, "Arena was reset after the marker was taken"),
+            BumpError::GrowsDown => write!(f, "Operation requires an upward-growing arena"),
+            BumpError::OffsetAhead => write!(f, "Offset is past the current cursor"),
+        }
+    }
+}
+
+/// A broken allocator invariant, reported by [`BumpAlloc::check_invariants`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The cursor lies outside the region.
+    CursorOutOfBounds {
+        /// Offset of the cursor from the base.
+        offset: usize,
+    },
+    /// The peak is lower than the bytes currently in use.
+    PeakBelowUsed {
+        /// Recorded peak.
+        peak: usize,
+        /// Bytes in use.
+        used: usize,
+    },
+    /// A recorded allocation is not aligned (or phased) as requested.
+    Misaligned(AllocRecord),
+    /// A recorded allocation extends past the cursor.
+    PastCursor(AllocRecord),
+    /// Two recorded allocations share memory.
+    Overlap(AllocRecord, AllocRecord),
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvariantError {}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::CursorOutOfBounds { offset } => {
+                write!(f, "Cursor at offset {} is outside the region", offset)
+            }
+            InvariantError::PeakBelowUsed { peak, used } => {
+                write!(
+                    f,
+                    "Peak of {} bytes is below the {} bytes in use",
+                    peak, used
+                )
+            }
+            InvariantError::Misaligned(r) if r.phase == 0 => write!(
+                f,
+                "Allocation at offset {} is not {}-byte aligned",
+                r.offset, r.align
+            ),
+            InvariantError::Misaligned(r) => write!(
+                f,
+                "Allocation at offset {} is not {} bytes past a {}-byte boundary",
+                r.offset, r.phase, r.align
+            ),
+            InvariantError::PastCursor(r) => write!(
+                f,
+                "Allocation at offset {} ({} bytes) extends past the cursor",
+                r.offset, r.size
+            ),
+            InvariantError::Overlap(a, b) => write!(
+                f,
+                "Allocations at offsets {} and {} overlap",
+                a.offset, b.offset
+            ),
+        }
+    }
+}
+
+/// Direction in which a [`BumpAlloc`] hands out memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrowDirection {
+    /// From the base toward the limit.
+    #[default]
+    Up,
+    /// From the limit toward the base.
+    Down,
+}
 
 /// A fast, lock-free bump allocator.
 ///
@@ -21,9 +367,85 @@ pub struct BumpAlloc {
     limit: NonNull<u8>,
     /// Current allocation cursor (atomically updated).
     cursor: AtomicUsize,
+    /// Highest number of bytes ever in use (preserved across resets).
+    peak: AtomicUsize,
+    /// Number of successful allocations (preserved across resets).
+    alloc_count: AtomicUsize,
+    /// Number of allocations that failed due to exhaustion (preserved across resets).
+    alloc_fail_count: AtomicUsize,
+    /// Alignment padding skipped by the most recent allocation.
+    last_padding: AtomicUsize,
+    /// Total alignment padding skipped (preserved across resets).
+    alignment_waste: AtomicUsize,
+    /// Distance between the start addresses of successive colored allocations.
+    color_stride: AtomicUsize,
+    /// Number of colored allocations attempted, selecting the next color.
+    next_color: AtomicUsize,
+    /// Padding skipped by colored allocations (preserved across resets).
+    color_waste: AtomicUsize,
+    /// Successful allocations per power-of-two size class.
+    #[cfg(feature = "stats-histogram")]
+    histogram: [AtomicUsize; SIZE_CLASSES],
+    /// Furthest cursor the arena was rewound from since the last secure
+    /// reset. Memory below it may still hold data past the current cursor.
+    dirty_end: AtomicUsize,
     /// Tracks whether the arena has been recycled (reset after use).
     /// Used to optimize zero-initialization in WitnessArena.
     is_recycled: AtomicBool,
+    /// Memory from the cursor up to here is known to be zero, as set by
+    /// `prezero`. Falls back to `base` whenever the cursor rewinds.
+    zeroed_end: AtomicUsize,
+    /// Whether the region is locked into physical memory.
+    locked: AtomicBool,
+    /// Largest alignment `alloc` accepts: the biggest power of two no larger
+    /// than the region.
+    max_align: usize,
+    /// Allocations rejected for exceeding `max_align`.
+    align_reject_count: AtomicUsize,
+    /// Failed cursor CAS attempts in `alloc`, i.e. contention retries.
+    cas_retries: AtomicUsize,
+    /// Number of resets so far; invalidates older markers.
+    resets: AtomicUsize,
+    /// Name reported in diagnostics such as `tracing` events.
+    label: &'static str,
+    /// Whether the region is a leaked `Box<[u8]>` freed on drop.
+    owned: bool,
+    /// Whether never-allocated memory is known to read as zero.
+    fresh_zeroed: bool,
+    /// Direction allocations grow in. The cursor always counts up from
+    /// `base`; for [`GrowDirection::Down`] blocks are mirrored onto the
+    /// region, see `mirror`.
+    direction: GrowDirection,
+    /// End of the committed part of a reserve-only region (`usize::MAX` when
+    /// the whole region is committed).
+    #[cfg(all(feature = "std", target_os = "windows"))]
+    committed: AtomicUsize,
+    /// Bytes from the base currently protected read-only (0 when unfrozen).
+    #[cfg(feature = "std")]
+    frozen_len: AtomicUsize,
+    /// Whether `reset` returns physical pages to the OS.
+    #[cfg(feature = "std")]
+    release_on_reset: AtomicBool,
+    /// Whether individual allocations are being recorded.
+    recording: AtomicBool,
+    /// Allocations made since the last reset, captured in record mode.
+    records: Mutex<Vec<AllocRecord>>,
+    /// Secret regions not securely wiped yet (debug builds only).
+    #[cfg(debug_assertions)]
+    tainted: Mutex<Vec<AllocRecord>>,
+    /// Optional [`ExhaustionHook`], stored as a raw function pointer.
+    exhaustion_hook: AtomicPtr<()>,
+    /// Context of the most recent failed allocation, captured in record mode.
+    last_exhaustion: Mutex<Option<ExhaustionContext>>,
+    /// Serializes allocations in place of the lock-free path, if present.
+    #[cfg(feature = "serialized")]
+    serial: Option<Mutex<()>>,
+    /// Whether exhaustion chains a new chunk instead of failing.
+    #[cfg(feature = "growable")]
+    growable: AtomicBool,
+    /// Next chunk in the chain, allocated on demand when growable.
+    #[cfg(feature = "growable")]
+    next: AtomicPtr<BumpAlloc>,
 }
 
 impl BumpAlloc {
@@ -38,214 +460,3267 @@ impl BumpAlloc {
 
         let base_nn = NonNull::new_unchecked(base);
         let limit_nn = NonNull::new_unchecked(base.add(size));
+        #[cfg(feature = "sanitizer")]
+        asan::poison(base as usize, size);
 
         Self {
             base: base_nn,
             limit: limit_nn,
             cursor: AtomicUsize::new(base as usize),
+            peak: AtomicUsize::new(0),
+            alloc_count: AtomicUsize::new(0),
+            alloc_fail_count: AtomicUsize::new(0),
+            last_padding: AtomicUsize::new(0),
+            alignment_waste: AtomicUsize::new(0),
+            color_stride: AtomicUsize::new(CACHE_COLOR_STRIDE),
+            next_color: AtomicUsize::new(0),
+            color_waste: AtomicUsize::new(0),
+            #[cfg(feature = "stats-histogram")]
+            histogram: [const { AtomicUsize::new(0) }; SIZE_CLASSES],
+            dirty_end: AtomicUsize::new(base as usize),
             is_recycled: AtomicBool::new(false),
+            zeroed_end: AtomicUsize::new(base as usize),
+            locked: AtomicBool::new(false),
+            max_align: 1 << size.ilog2(),
+            align_reject_count: AtomicUsize::new(0),
+            cas_retries: AtomicUsize::new(0),
+            resets: AtomicUsize::new(0),
+            label: "bump",
+            owned: false,
+            fresh_zeroed: false,
+            direction: GrowDirection::Up,
+            #[cfg(all(feature = "std", target_os = "windows"))]
+            committed: AtomicUsize::new(usize::MAX),
+            #[cfg(feature = "std")]
+            frozen_len: AtomicUsize::new(0),
+            #[cfg(feature = "std")]
+            release_on_reset: AtomicBool::new(false),
+            recording: AtomicBool::new(false),
+            records: Mutex::new(Vec::new()),
+            #[cfg(debug_assertions)]
+            tainted: Mutex::new(Vec::new()),
+            last_exhaustion: Mutex::new(None),
+            exhaustion_hook: AtomicPtr::new(core::ptr::null_mut()),
+            #[cfg(feature = "serialized")]
+            serial: None,
+            #[cfg(feature = "growable")]
+            growable: AtomicBool::new(false),
+            #[cfg(feature = "growable")]
+            next: AtomicPtr::new(core::ptr::null_mut()),
         }
     }
 
-    /// Get the base pointer of this allocator.
+    /// Create a bump allocator over a zero-filled memory block, such as a
+    /// fresh OS mapping.
+    ///
+    /// [`alloc_zeroed`](Self::alloc_zeroed) then skips zeroing until the
+    /// arena is first reset.
+    ///
+    /// # Safety
+    /// The memory block `[base, base+size)` must be valid, writable and
+    /// zero-filled.
     #[inline]
-    pub fn base_ptr(&self) -> *mut u8 {
-        self.base.as_ptr()
+    pub unsafe fn new_zeroed(base: *mut u8, size: usize) -> Self {
+        let mut alloc = Self::new(base, size);
+        alloc.fresh_zeroed = true;
+        alloc
     }
 
-    /// Allocate memory with the given size and alignment.
+    /// Create a bump allocator over a caller-provided static buffer.
     ///
-    /// Returns a null pointer if there is not enough space.
-    #[inline(always)]
-    pub fn alloc(&self, size: usize, align: usize) -> *mut u8 {
-        debug_assert!(size > 0);
-        debug_assert!(align > 0);
-        debug_assert!(align.is_power_of_two());
+    /// Use this where the platform layer is unavailable (SGX enclaves,
+    /// WASM, `no_std` firmware). For a buffer with a shorter lifetime, use
+    /// [`new`](Self::new) and keep the buffer alive yourself.
+    ///
+    /// # Panics
+    /// Panics if `buf` is empty.
+    pub fn from_slice(buf: &'static mut [u8]) -> Self {
+        assert!(!buf.is_empty(), "BumpAlloc buffer must not be empty");
+        unsafe { Self::new(buf.as_mut_ptr(), buf.len()) }
+    }
 
-        loop {
-            let current = self.cursor.load(Ordering::Relaxed);
-            let aligned = (current + align - 1) & !(align - 1);
-            let next = aligned + size;
-
-            if next > self.limit.as_ptr() as usize {
-                // Arena exhausted - log in debug mode
-                #[cfg(debug_assertions)]
-                {
-                    eprintln!(
-                        "[nalloc] Arena exhausted: requested {} bytes (align {}), remaining {} bytes",
-                        size, align, self.remaining()
-                    );
-                }
-                return std::ptr::null_mut();
+    /// Create a bump allocator that owns `buf` and frees it on drop.
+    ///
+    /// # Panics
+    /// Panics if `buf` is empty.
+    pub fn from_boxed(buf: Box<[u8]>) -> Self {
+        assert!(!buf.is_empty(), "BumpAlloc buffer must not be empty");
+        let len = buf.len();
+        let base = Box::into_raw(buf) as *mut u8;
+        let mut alloc = unsafe { Self::new(base, len) };
+        alloc.owned = true;
+        alloc
+    }
+
+    /// Create a bump allocator whose allocations are serialized by a mutex.
+    ///
+    /// Concurrent lock-free allocation hands out offsets in whatever order
+    /// the CAS races resolve. In serialized mode each allocation holds a
+    /// lock for its whole duration, so offsets depend only on the order in
+    /// which requests are issued. This trades throughput for reproducible
+    /// layouts when debugging or comparing against golden files.
+    ///
+    /// # Safety
+    /// The memory block `[base, base+size)` must be valid and writable.
+    #[cfg(feature = "serialized")]
+    #[inline]
+    pub unsafe fn new_serialized(base: *mut u8, size: usize) -> Self {
+        let mut alloc = Self::new(base, size);
+        alloc.serial = Some(Mutex::new(()));
+        alloc
+    }
+
+    /// Name this arena in diagnostics, e.g. the `arena` field of the
+    /// allocation events emitted with the `tracing` feature.
+    #[inline]
+    pub fn with_label(mut self, label: &'static str) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Hand out memory in `direction`, e.g. [`GrowDirection::Down`] for the
+    /// upper of two arenas sharing one buffer so they grow toward each
+    /// other.
+    ///
+    /// A downward arena returns each block just below the previous one;
+    /// `used`, `remaining`, markers and resets behave as for an upward one.
+    /// Blocks cannot be resized in place ([`grow_in_place`](Self::grow_in_place)
+    /// and [`shrink_in_place`](Self::shrink_in_place) fail, except for
+    /// [`dealloc_last`](Self::dealloc_last)), and
+    /// [`reset_keeping_last`](Self::reset_keeping_last) fails with
+    /// [`BumpError::GrowsDown`]. Chunks chained by a growable arena always
+    /// grow upward.
+    #[inline]
+    pub fn with_direction(mut self, direction: GrowDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Get the direction set with [`with_direction`](Self::with_direction).
+    #[inline]
+    pub fn direction(&self) -> GrowDirection {
+        self.direction
+    }
+
+    /// Map the `len`-byte range starting at cursor address `start` onto the
+    /// region, or back. The identity for upward arenas; downward ones
+    /// mirror it around the middle of the region.
+    #[inline(always)]
+    fn mirror(&self, start: usize, len: usize) -> usize {
+        match self.direction {
+            GrowDirection::Up => start,
+            // Not `base + limit - ...`: the sum can overflow on 32-bit.
+            GrowDirection::Down => {
+                self.limit.as_ptr() as usize - (start - self.base.as_ptr() as usize) - len
             }
+        }
+    }
 
-            if self
-                .cursor
-                .compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::Relaxed)
-                .is_ok()
-            {
-                return aligned as *mut u8;
+    /// Phase a mirrored `size`-byte block needs in cursor space for its
+    /// region address to lie `phase` bytes past an `align` boundary.
+    #[inline(always)]
+    fn cursor_phase(&self, size: usize, align: usize, phase: usize) -> usize {
+        match self.direction {
+            GrowDirection::Up => phase,
+            // Only the residue matters, so the sum may wrap.
+            GrowDirection::Down => {
+                (self.base.as_ptr() as usize)
+                    .wrapping_add(self.limit.as_ptr() as usize)
+                    .wrapping_sub(size)
+                    .wrapping_sub(phase)
+                    & (align - 1)
             }
-            // Contention: another thread allocated concurrently. Retry.
         }
     }
 
-    /// Check if this arena has been recycled (reset after initial use).
+    /// Get the name set with [`with_label`](Self::with_label) (`"bump"` by default).
     #[inline]
-    pub fn is_recycled(&self) -> bool {
-        self.is_recycled.load(Ordering::Relaxed)
+    pub fn label(&self) -> &'static str {
+        self.label
     }
 
-    /// Reset the bump pointer to the base.
-    ///
-    /// # Safety
-    /// All previously allocated memory becomes invalid after this call.
+    /// Get the base pointer of this allocator.
     #[inline]
-    pub unsafe fn reset(&self) {
-        self.cursor
-            .store(self.base.as_ptr() as usize, Ordering::SeqCst);
-        self.is_recycled.store(true, Ordering::Release);
+    pub fn base_ptr(&self) -> *mut u8 {
+        self.base.as_ptr()
     }
 
-    /// Zero out all memory in the arena and reset the cursor.
-    ///
-    /// This is critical for security-sensitive applications like ZK provers,
-    /// where witness data must be wiped after use to prevent leakage.
+    /// Allocate memory with the given size and alignment.
     ///
-    /// Uses volatile writes to prevent the compiler from optimizing away
-    /// the zeroing operation (dead store elimination).
+    /// Returns a null pointer if there is not enough space.
+    #[inline(always)]
+    pub fn alloc(&self, size: usize, align: usize) -> *mut u8 {
+        self.alloc_with_phase(size, align, 0)
+    }
+
+    /// Allocate like [`alloc`](Self::alloc), also returning the offset of
+    /// the block from [`base_ptr`](Self::base_ptr).
     ///
-    /// # Safety
-    /// All previously allocated memory becomes invalid after this call.
+    /// The offset stays meaningful wherever the region is mapped, so it can
+    /// stand in for the pointer in serialized or pointer-dense structures
+    /// (e.g. as a `u32` when the region is smaller than 4 GB). Returns
+    /// `None` if the arena is exhausted. A growable arena's chunks lie
+    /// outside the region, so a block placed in one is reported as `None`
+    /// too, though it stays allocated until the next reset.
     #[inline]
-    pub unsafe fn secure_reset(&self) {
-        let base = self.base.as_ptr();
-        let size = self.limit.as_ptr() as usize - base as usize;
+    pub fn alloc_offset(&self, size: usize, align: usize) -> Option<(usize, *mut u8)> {
+        let ptr = self.alloc(size, align);
+        let base = self.base.as_ptr() as usize;
+        let offset = (ptr as usize).wrapping_sub(base);
+        (!ptr.is_null() && offset < self.limit.as_ptr() as usize - base).then_some((offset, ptr))
+    }
 
-        // Use volatile writes to prevent dead store elimination.
-        // This ensures the memory is actually zeroed even if it's never read again.
-        Self::volatile_memset(base, SECURE_WIPE_PATTERN, size);
+    /// Allocate `count` slots of `size` bytes in a single cursor update.
+    ///
+    /// Slot `i` starts at `base + i * stride`, where `stride` is `size`
+    /// rounded up to `align`, so every slot is aligned. This replaces
+    /// `count` separate allocations (and their compare-and-swaps) with one,
+    /// and counts as one allocation in the statistics. Returns a null
+    /// pointer if the whole block does not fit or its size overflows.
+    #[inline]
+    pub fn alloc_n(&self, count: usize, size: usize, align: usize) -> *mut u8 {
+        debug_assert!(count > 0);
+        debug_assert!(align.is_power_of_two());
+        match size
+            .checked_next_multiple_of(align)
+            .and_then(|stride| stride.checked_mul(count))
+        {
+            Some(total) => self.alloc(total, align),
+            None => core::ptr::null_mut(),
+        }
+    }
 
-        // Compiler fence to ensure the wipe completes before any subsequent operations.
-        compiler_fence(Ordering::SeqCst);
+    /// Allocate memory for `layout`. Same as [`alloc`](Self::alloc) with
+    /// the layout's size and alignment.
+    #[inline(always)]
+    pub fn alloc_layout(&self, layout: Layout) -> *mut u8 {
+        self.alloc(layout.size(), layout.align())
+    }
 
-        self.reset();
+    /// Allocate memory whose address lies `phase` bytes past an `align` boundary.
+    ///
+    /// That is, the returned address `a` satisfies `a % align == phase`.
+    /// With `phase == 0` this is identical to [`alloc`](Self::alloc).
+    ///
+    /// Returns a null pointer if there is not enough space.
+    #[inline(always)]
+    pub fn alloc_with_phase(&self, size: usize, align: usize, phase: usize) -> *mut u8 {
+        let ptr = self.alloc_untraced(size, align, phase);
+        #[cfg(feature = "tracing")]
+        self.trace_alloc(size, align, ptr);
+        ptr
     }
 
-    /// Volatile memset implementation that cannot be optimized away.
+    /// Emit the allocation event for a request that returned `ptr`.
     ///
-    /// This is critical for cryptographic security - we need to guarantee
-    /// that sensitive data is actually erased from memory.
+    /// `offset` is relative to the initial region, so allocations from a
+    /// growable arena's chunks report an out-of-range offset.
+    #[cfg(feature = "tracing")]
     #[inline(never)]
-    unsafe fn volatile_memset(ptr: *mut u8, value: u8, len: usize) {
-        // Method 1: Use platform-specific secure zeroing where available
-        #[cfg(any(target_os = "linux", target_os = "android"))]
-        {
-            // explicit_bzero is guaranteed not to be optimized away
-            extern "C" {
-                fn explicit_bzero(s: *mut libc::c_void, n: libc::size_t);
-            }
-            if value == 0 {
-                explicit_bzero(ptr as *mut libc::c_void, len);
-            }
-        }
+    fn trace_alloc(&self, size: usize, align: usize, ptr: *mut u8) {
+        tracing::trace!(
+            target: "nalloc",
+            arena = self.label,
+            size,
+            align,
+            offset = (ptr as usize).wrapping_sub(self.base.as_ptr() as usize),
+            success = !ptr.is_null(),
+        );
+    }
 
-        #[cfg(target_vendor = "apple")]
-        {
-            // memset_s is guaranteed not to be optimized away (C11)
-            extern "C" {
-                fn memset_s(
-                    s: *mut libc::c_void,
-                    smax: libc::size_t,
-                    c: libc::c_int,
-                    n: libc::size_t,
-                ) -> libc::c_int;
-            }
-            let _ = memset_s(ptr as *mut libc::c_void, len, value as libc::c_int, len);
+    #[inline(always)]
+    fn alloc_untraced(&self, size: usize, align: usize, phase: usize) -> *mut u8 {
+        self.alloc_padded(size, align, phase).0
+    }
+
+    /// Allocate like [`alloc_with_phase`](Self::alloc_with_phase), also
+    /// returning the padding skipped (0 for a block from a chunk).
+    #[inline(always)]
+    fn alloc_padded(&self, size: usize, align: usize, phase: usize) -> (*mut u8, usize) {
+        debug_assert!(size > 0);
+        debug_assert!(align > 0);
+        debug_assert!(align.is_power_of_two());
+        debug_assert!(phase < align);
+
+        if align > self.max_align {
+            return (self.reject_align(), 0);
         }
 
-        // Fallback: Volatile write loop (works everywhere, slightly slower)
-        #[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
-        {
-            for i in 0..len {
-                std::ptr::write_volatile(ptr.add(i), value);
+        #[cfg(feature = "serialized")]
+        let _guard = self.serial.as_ref().map(Mutex::lock);
+
+        match self.bump(size, align, self.cursor_phase(size, align, phase)) {
+            Some((aligned, next, padding)) => {
+                #[cfg(feature = "verify-invariants")]
+                self.verify_block(aligned - padding, aligned, size, align, phase);
+                let aligned = self.mirror(aligned, size);
+                #[cfg(feature = "sanitizer")]
+                asan::unpoison(aligned, size);
+                let used = next - self.base.as_ptr() as usize;
+                if used > self.peak.load(Ordering::Relaxed) {
+                    self.peak.fetch_max(used, Ordering::Relaxed);
+                }
+                self.alloc_count.fetch_add(1, Ordering::Relaxed);
+                self.count_padding(padding);
+                #[cfg(feature = "stats-histogram")]
+                self.histogram[size_class(size)].fetch_add(1, Ordering::Relaxed);
+                if self.recording.load(Ordering::Relaxed) {
+                    self.record(aligned, size, align, phase);
+                }
+                (aligned as *mut u8, padding)
             }
+            None => (self.alloc_exhausted(size, align, phase), 0),
         }
     }
 
-    /// Returns the total capacity in bytes.
+    /// Allocate `size` bytes, 64-byte aligned, on the next cache color.
+    ///
+    /// Successive calls place their blocks at rising offsets within the
+    /// [`CACHE_COLOR_SPAN`], [`color_stride`](Self::color_stride) bytes
+    /// apart and wrapping around after `CACHE_COLOR_SPAN / color_stride`
+    /// colors. Large buffers then start in different cache sets instead of
+    /// all sharing the same low address bits. Each call may pad up to
+    /// `CACHE_COLOR_SPAN` bytes; see [`color_waste`](Self::color_waste).
+    ///
+    /// A region smaller than the span cannot be colored and falls back to
+    /// a plain 64-byte aligned allocation.
     #[inline]
-    pub fn capacity(&self) -> usize {
-        self.limit.as_ptr() as usize - self.base.as_ptr() as usize
+    pub fn alloc_colored(&self, size: usize) -> *mut u8 {
+        if CACHE_COLOR_SPAN > self.max_align {
+            return self.alloc(size, CACHE_LINE_ALIGN);
+        }
+        let stride = self.color_stride.load(Ordering::Relaxed);
+        let color = self.next_color.fetch_add(1, Ordering::Relaxed) % (CACHE_COLOR_SPAN / stride);
+        let (ptr, padding) = self.alloc_padded(size, CACHE_COLOR_SPAN, color * stride);
+        #[cfg(feature = "tracing")]
+        self.trace_alloc(size, CACHE_COLOR_SPAN, ptr);
+        if padding > 0 {
+            self.color_waste.fetch_add(padding, Ordering::Relaxed);
+        }
+        ptr
     }
 
-    /// Returns the number of bytes currently allocated.
+    /// Set the distance between successive colors of
+    /// [`alloc_colored`](Self::alloc_colored). Defaults to
+    /// [`CACHE_COLOR_STRIDE`].
+    ///
+    /// # Panics
+    /// If `stride` is not a power of two between [`CACHE_LINE_ALIGN`] and
+    /// half the [`CACHE_COLOR_SPAN`].
     #[inline]
-    pub fn used(&self) -> usize {
-        self.cursor.load(Ordering::Relaxed) - self.base.as_ptr() as usize
+    pub fn set_color_stride(&self, stride: usize) {
+        assert!(
+            stride.is_power_of_two() && (CACHE_LINE_ALIGN..=CACHE_COLOR_SPAN / 2).contains(&stride),
+            "color stride must be a power of two between 64 bytes and half the color span"
+        );
+        self.color_stride.store(stride, Ordering::Relaxed);
     }
 
-    /// Returns the number of bytes remaining.
+    /// Returns the current coloring stride.
     #[inline]
-    pub fn remaining(&self) -> usize {
-        self.capacity() - self.used()
+    pub fn color_stride(&self) -> usize {
+        self.color_stride.load(Ordering::Relaxed)
     }
-}
-
-// Safety: BumpAlloc can be shared across threads because:
-// - `base` and `limit` are never modified after construction
-// - `cursor` uses atomic operations for thread-safe updates
-// - `is_recycled` uses atomic operations
-unsafe impl Send for BumpAlloc {}
-unsafe impl Sync for BumpAlloc {}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    #[test]
-    fn test_nonnull_safety() {
-        let mut buffer = vec![0u8; 1024];
-        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+    /// Returns the color the next [`alloc_colored`](Self::alloc_colored)
+    /// call will use, in `0..CACHE_COLOR_SPAN / color_stride`.
+    #[inline]
+    pub fn next_color(&self) -> usize {
+        self.next_color.load(Ordering::Relaxed) % (CACHE_COLOR_SPAN / self.color_stride())
+    }
 
-        assert_eq!(alloc.capacity(), 1024);
-        assert_eq!(alloc.used(), 0);
-        assert_eq!(alloc.remaining(), 1024);
-        assert!(!alloc.is_recycled());
+    /// Returns the padding skipped to place colored allocations.
+    ///
+    /// Preserved across resets. Also included in
+    /// [`alignment_waste`](Self::alignment_waste).
+    #[inline]
+    pub fn color_waste(&self) -> usize {
+        self.color_waste.load(Ordering::Relaxed)
     }
 
-    #[test]
-    fn test_recycled_flag() {
-        let mut buffer = vec![0u8; 1024];
-        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+    /// Move the cursor past a `size`-byte block, returning its address, the
+    /// new cursor and the padding skipped to align it, or `None` if this
+    /// region cannot fit it.
+    #[inline(always)]
+    fn bump(&self, size: usize, align: usize, phase: usize) -> Option<(usize, usize, usize)> {
+        loop {
+            let current = self.cursor.load(Ordering::Relaxed);
+            let (aligned, next) = self.fit(current, size, align, phase)?;
+
+            if self
+                .cursor
+                .compare_exchange_weak(current, next, Ordering::SeqCst, Ordering::Relaxed)
+                .is_ok()
+            {
+                #[cfg(all(feature = "std", target_os = "windows"))]
+                if !self.commit_to(next) {
+                    // Out of commit charge: give the space back if nothing
+                    // was allocated after it, and report exhaustion.
+                    let _ = self.cursor.compare_exchange(
+                        next,
+                        current,
+                        Ordering::SeqCst,
+                        Ordering::Relaxed,
+                    );
+                    return None;
+                }
+                return Some((aligned, next, aligned - current));
+            }
+            // Contention: another thread allocated concurrently. Retry.
+            self.cas_retries.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Panic unless a `size`-byte block placed at cursor address `aligned`,
+    /// with the cursor at `current` before, lies past every earlier block
+    /// and inside the region, aligned as requested.
+    #[cfg(feature = "verify-invariants")]
+    #[track_caller]
+    fn verify_block(
+        &self,
+        current: usize,
+        aligned: usize,
+        size: usize,
+        align: usize,
+        phase: usize,
+    ) {
+        let in_bounds = aligned
+            .checked_add(size)
+            .is_some_and(|end| end <= self.limit.as_ptr() as usize);
+        assert!(
+            aligned >= current && in_bounds,
+            "block of {} bytes at offset {} overlaps earlier blocks or the end of arena '{}' (cursor at offset {})",
+            size,
+            aligned.wrapping_sub(self.base.as_ptr() as usize),
+            self.label,
+            current - self.base.as_ptr() as usize
+        );
+        let addr = self.mirror(aligned, size);
+        assert!(
+            addr % align == phase,
+            "block at {:#x} in arena '{}' misses its alignment of {} (phase {})",
+            addr,
+            self.label,
+            align,
+            phase
+        );
+    }
+
+    /// Record the padding between the pre-allocation cursor and the block.
+    #[inline(always)]
+    fn count_padding(&self, padding: usize) {
+        self.last_padding.store(padding, Ordering::Relaxed);
+        if padding > 0 {
+            self.alignment_waste.fetch_add(padding, Ordering::Relaxed);
+        }
+    }
+
+    /// Place a `size`-byte block at or after `current`, returning its address
+    /// and the end of the block, or `None` if it would pass the limit.
+    #[inline(always)]
+    fn fit(
+        &self,
+        current: usize,
+        size: usize,
+        align: usize,
+        phase: usize,
+    ) -> Option<(usize, usize)> {
+        // Checked so a huge `size` or `align` cannot wrap around to an
+        // address below the limit.
+        let aligned = ((current - phase).checked_add(align - 1)? & !(align - 1)) + phase;
+        let next = aligned.checked_add(size)?;
+        (next <= self.limit.as_ptr() as usize).then_some((aligned, next))
+    }
+
+    /// Check whether an allocation of `size` bytes at `align` would fit in
+    /// the region right now, including the padding needed to align the
+    /// current cursor.
+    ///
+    /// Nothing is reserved, so a concurrent allocation can still take the
+    /// space first. Chunks a growable arena would add are not considered.
+    #[inline]
+    pub fn can_alloc(&self, size: usize, align: usize) -> bool {
+        debug_assert!(align.is_power_of_two());
+        align <= self.max_align
+            && self
+                .fit(
+                    self.cursor.load(Ordering::Relaxed),
+                    size,
+                    align,
+                    self.cursor_phase(size, align, 0),
+                )
+                .is_some()
+    }
+
+    /// Slow path taken when a request's alignment exceeds `max_align`.
+    #[cold]
+    fn reject_align(&self) -> *mut u8 {
+        self.align_reject_count.fetch_add(1, Ordering::Relaxed);
+        core::ptr::null_mut()
+    }
+
+    /// Slow path taken when the region cannot fit a request.
+    #[cold]
+    fn alloc_exhausted(&self, size: usize, align: usize, phase: usize) -> *mut u8 {
+        #[cfg(feature = "growable")]
+        if self.growable.load(Ordering::Relaxed) {
+            if let Some(addr) = self.alloc_from_chunks(size, align, phase) {
+                self.peak.fetch_max(self.used(), Ordering::Relaxed);
+                self.alloc_count.fetch_add(1, Ordering::Relaxed);
+                return addr as *mut u8;
+            }
+        }
+        #[cfg(not(feature = "growable"))]
+        let _ = phase;
+
+        let hook = self.exhaustion_hook.load(Ordering::Acquire);
+        if !hook.is_null() {
+            // Safety: only ever stored from an `ExhaustionHook`.
+            let hook: ExhaustionHook = unsafe { core::mem::transmute(hook) };
+            hook(size, align, self.remaining());
+        }
+        self.alloc_fail_count.fetch_add(1, Ordering::Relaxed);
+        if self.recording.load(Ordering::Relaxed) {
+            self.capture_exhaustion(size, align);
+        }
+        core::ptr::null_mut()
+    }
+
+    /// Install a callback to run whenever an allocation fails, or remove it
+    /// with `None`. No callback is installed by default.
+    ///
+    /// See [`ExhaustionHook`] for the restrictions on what it may do.
+    #[inline]
+    pub fn set_exhaustion_hook(&self, hook: Option<ExhaustionHook>) {
+        let raw = hook.map_or(core::ptr::null_mut(), |f| f as *mut ());
+        self.exhaustion_hook.store(raw, Ordering::Release);
+    }
+
+    /// Allow the arena to grow past its initial region.
+    ///
+    /// When enabled, a request the current region cannot fit maps a new
+    /// chunk (at least double the size of the previous one) and bumps from
+    /// there instead of failing. Chunks are returned to the OS on reset, so
+    /// only the initial region has stable addresses across resets.
+    /// [`checkpoint`](Self::checkpoint) and [`restore`](Self::restore) only
+    /// rewind the initial region.
+    #[cfg(feature = "growable")]
+    #[inline]
+    pub fn set_growable(&self, enabled: bool) {
+        self.growable.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Check if the arena grows by chaining chunks on exhaustion.
+    #[cfg(feature = "growable")]
+    #[inline]
+    pub fn is_growable(&self) -> bool {
+        self.growable.load(Ordering::Relaxed)
+    }
+
+    /// Walk the chunk chain, mapping a new chunk at the end if none fits.
+    #[cfg(feature = "growable")]
+    fn alloc_from_chunks(&self, size: usize, align: usize, phase: usize) -> Option<usize> {
+        let mut link = &self.next;
+        let mut prev_len = self.region_len();
+        loop {
+            let mut chunk = link.load(Ordering::Acquire);
+            if chunk.is_null() {
+                let needed = size.checked_add(align)?;
+                let fresh = Self::map_chunk(needed.max(prev_len.saturating_mul(2)))?;
+                chunk = match link.compare_exchange(
+                    core::ptr::null_mut(),
+                    fresh,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => fresh,
+                    Err(winner) => {
+                        // Another thread grew the chain first; use its chunk.
+                        unsafe { Self::unmap_chunk(fresh) };
+                        winner
+                    }
+                };
+            }
+
+            let current = unsafe { &*chunk };
+            if let Some((aligned, _, _)) = current.bump(size, align, phase) {
+                #[cfg(feature = "sanitizer")]
+                asan::unpoison(aligned, size);
+                return Some(aligned);
+            }
+            prev_len = current.region_len();
+            link = &current.next;
+        }
+    }
+
+    /// Map a chunk able to hold `len` bytes, with its header at the front.
+    #[cfg(feature = "growable")]
+    fn map_chunk(len: usize) -> Option<*mut BumpAlloc> {
+        let header =
+            (core::mem::size_of::<BumpAlloc>() + CACHE_LINE_ALIGN - 1) & !(CACHE_LINE_ALIGN - 1);
+        let page = sys::page_size();
+        let total = len.checked_add(header + page - 1)? & !(page - 1);
+        let ptr = sys::alloc(total).ok()?;
+        unsafe {
+            let chunk = ptr as *mut BumpAlloc;
+            chunk.write(Self::new_zeroed(ptr.add(header), total - header));
+            Some(chunk)
+        }
+    }
+
+    /// Unmap a chunk created by [`map_chunk`](Self::map_chunk).
+    #[cfg(feature = "growable")]
+    unsafe fn unmap_chunk(chunk: *mut BumpAlloc) {
+        let total = (*chunk).limit.as_ptr() as usize - chunk as usize;
+        core::ptr::drop_in_place(chunk);
+        let _ = sys::dealloc(chunk as *mut u8, total);
+    }
+
+    /// Unmap every chained chunk, leaving only the initial region.
+    #[cfg(feature = "growable")]
+    unsafe fn free_chunks(&self) {
+        let mut chunk = self.next.swap(core::ptr::null_mut(), Ordering::AcqRel);
+        while !chunk.is_null() {
+            let next = (*chunk).next.swap(core::ptr::null_mut(), Ordering::AcqRel);
+            Self::unmap_chunk(chunk);
+            chunk = next;
+        }
+    }
+
+    /// Apply `f` to every chained chunk.
+    #[cfg(feature = "growable")]
+    fn for_each_chunk(&self, mut f: impl FnMut(&BumpAlloc)) {
+        let mut chunk = self.next.load(Ordering::Acquire);
+        while let Some(current) = unsafe { chunk.as_ref() } {
+            f(current);
+            chunk = current.next.load(Ordering::Acquire);
+        }
+    }
+
+    /// Sum `f` over every chained chunk.
+    #[cfg(feature = "growable")]
+    fn chunk_sum(&self, f: impl Fn(&BumpAlloc) -> usize) -> usize {
+        let mut total = 0;
+        self.for_each_chunk(|chunk| total += f(chunk));
+        total
+    }
+
+    /// Allocate zero-initialized memory.
+    ///
+    /// Memory in a region created with [`new_zeroed`](Self::new_zeroed) is
+    /// only zeroed here once the arena has been recycled; before that it is
+    /// still zero from the OS. Memory zeroed ahead of time by
+    /// [`prezero`](Self::prezero) is not zeroed again either.
+    #[inline]
+    pub fn alloc_zeroed(&self, size: usize, align: usize) -> *mut u8 {
+        let ptr = self.alloc(size, align);
+        if !ptr.is_null()
+            && (self.is_recycled() || !self.fresh_zeroed)
+            && self.mirror(ptr as usize, size) + size > self.zeroed_end.load(Ordering::Acquire)
+        {
+            unsafe { core::ptr::write_bytes(ptr, 0, size) };
+        }
+        ptr
+    }
+
+    /// Allocate a `total_len`-byte block whose bytes past `used_len` are
+    /// zero, leaving the first `used_len` bytes uninitialized.
+    ///
+    /// Like [`alloc_zeroed`](Self::alloc_zeroed), but only the tail is
+    /// zeroed, and only if it lies in memory handed out before.
+    #[inline]
+    pub fn alloc_zero_padded(&self, used_len: usize, total_len: usize, align: usize) -> *mut u8 {
+        debug_assert!(used_len <= total_len);
+        let ptr = self.alloc(total_len, align);
+        let used_len = used_len.min(total_len);
+        if !ptr.is_null() && used_len < total_len && (self.is_recycled() || !self.fresh_zeroed) {
+            let tail = unsafe { ptr.add(used_len) };
+            let len = total_len - used_len;
+            if self.mirror(tail as usize, len) + len > self.zeroed_end.load(Ordering::Acquire) {
+                unsafe { core::ptr::write_bytes(tail, 0, len) };
+            }
+        }
+        ptr
+    }
+
+    /// Zero the next `bytes` of free memory (clamped to the region) ahead
+    /// of time.
+    ///
+    /// The pages are faulted in (committed on Windows) and, until the
+    /// cursor next rewinds, [`alloc_zeroed`](Self::alloc_zeroed) skips
+    /// zeroing blocks within them. This moves both costs out of the
+    /// allocations that follow.
+    ///
+    /// # Safety
+    /// No allocation may run concurrently with this call.
+    pub unsafe fn prezero(&self, bytes: usize) {
+        let start = self.cursor.load(Ordering::SeqCst);
+        let end = start
+            .saturating_add(bytes)
+            .min(self.limit.as_ptr() as usize);
+        #[cfg(all(feature = "std", target_os = "windows"))]
+        if !self.commit_to(end) {
+            return;
+        }
+        let zeroed = self.mirror(start, end - start);
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(zeroed, end - start);
+        core::ptr::write_bytes(zeroed as *mut u8, 0, end - start);
+        #[cfg(feature = "sanitizer")]
+        asan::poison(zeroed, end - start);
+        self.zeroed_end.fetch_max(end, Ordering::Release);
+    }
+
+    /// Poison everything past the cursor for ASan.
+    #[cfg(feature = "sanitizer")]
+    fn poison_free(&self) {
+        let cursor = self.cursor.load(Ordering::SeqCst);
+        let len = self.limit.as_ptr() as usize - cursor;
+        asan::poison(self.mirror(cursor, len), len);
+    }
+
+    /// Record that the cursor rewound, so memory past it may hold old data.
+    #[inline]
+    fn mark_recycled(&self) {
+        self.zeroed_end
+            .store(self.base.as_ptr() as usize, Ordering::Release);
+        self.is_recycled.store(true, Ordering::Release);
+    }
+
+    /// Allocate memory, returning `None` instead of a null pointer on failure.
+    ///
+    /// Semantics are identical to [`alloc`](Self::alloc), but success and
+    /// failure are encoded in the type.
+    #[inline(always)]
+    pub fn alloc_nn(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        NonNull::new(self.alloc(size, align))
+    }
+
+    /// Grow the most recent allocation in place.
+    ///
+    /// If `ptr + old_size` is exactly the cursor, i.e. `ptr` is the last
+    /// block handed out, the cursor is moved to `ptr + new_size` and `true`
+    /// is returned. Otherwise, or if the arena cannot fit the larger block,
+    /// nothing changes and `false` is returned. Always fails for an arena
+    /// growing [down](GrowDirection::Down).
+    #[inline]
+    pub fn grow_in_place(&self, ptr: *mut u8, old_size: usize, new_size: usize) -> bool {
+        debug_assert!(new_size >= old_size);
+        if self.direction == GrowDirection::Down {
+            return false;
+        }
+
+        #[cfg(feature = "serialized")]
+        let _guard = self.serial.as_ref().map(Mutex::lock);
+
+        let addr = ptr as usize;
+        let (Some(end), Some(new_end)) = (addr.checked_add(old_size), addr.checked_add(new_size))
+        else {
+            return false;
+        };
+        // The arena before this one may end where this one starts, so a
+        // block of its own can sit right at this cursor.
+        if addr < self.base.as_ptr() as usize || new_end > self.limit.as_ptr() as usize {
+            return false;
+        }
+        if self
+            .cursor
+            .compare_exchange(end, new_end, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        #[cfg(all(feature = "std", target_os = "windows"))]
+        if !self.commit_to(new_end) {
+            let _ = self
+                .cursor
+                .compare_exchange(new_end, end, Ordering::SeqCst, Ordering::Relaxed);
+            return false;
+        }
+
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(end, new_size - old_size);
+        self.peak
+            .fetch_max(new_end - self.base.as_ptr() as usize, Ordering::Relaxed);
+        if self.recording.load(Ordering::Relaxed) {
+            let offset = addr - self.base.as_ptr() as usize;
+            if let Some(rec) = self.lock_records().iter_mut().find(|r| r.offset == offset) {
+                rec.size = new_size;
+            }
+        }
+        true
+    }
+
+    /// Give back the most recent allocation.
+    ///
+    /// If `ptr + size` is exactly the cursor, i.e. `ptr` is the last block
+    /// handed out, the cursor is rewound to `ptr` and `true` is returned.
+    /// Otherwise this is a no-op returning `false`, like deallocation in
+    /// general. LIFO allocate/free patterns therefore reclaim their memory.
+    ///
+    /// # Safety
+    /// `ptr` must be a live allocation of `size` bytes from this arena; it
+    /// must not be used after this call.
+    #[inline]
+    pub unsafe fn dealloc_last(&self, ptr: *mut u8, size: usize) -> bool {
+        self.shrink_in_place(ptr, size, 0)
+    }
+
+    /// Shrink the most recent allocation in place.
+    ///
+    /// If `ptr + old_size` is exactly the cursor, the cursor is moved back
+    /// to `ptr + new_size` and `true` is returned; otherwise nothing
+    /// changes. A `new_size` of zero gives the block back entirely, like
+    /// [`dealloc_last`](Self::dealloc_last), which is the only shrink an
+    /// arena growing [down](GrowDirection::Down) supports.
+    ///
+    /// # Safety
+    /// `ptr` must be a live allocation of `old_size` bytes from this arena;
+    /// the bytes past `new_size` must not be used after this call.
+    #[inline]
+    pub unsafe fn shrink_in_place(&self, ptr: *mut u8, old_size: usize, new_size: usize) -> bool {
+        debug_assert!(new_size <= old_size);
+        let addr = ptr as usize;
+        let Some(end) = addr.checked_add(old_size) else {
+            return false;
+        };
+        if addr < self.base.as_ptr() as usize || end > self.limit.as_ptr() as usize {
+            return false;
+        }
+        if self.direction == GrowDirection::Down && new_size > 0 {
+            return false;
+        }
+        let start = self.mirror(addr, old_size);
+        let end = start + old_size;
+        let new_end = start + new_size;
+        if self
+            .cursor
+            .compare_exchange(end, new_end, Ordering::SeqCst, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+
+        #[cfg(feature = "sanitizer")]
+        asan::poison(
+            self.mirror(new_end, old_size - new_size),
+            old_size - new_size,
+        );
+        self.dirty_end.fetch_max(end, Ordering::Relaxed);
+        self.mark_recycled();
+        if self.recording.load(Ordering::Relaxed) {
+            let offset = addr - self.base.as_ptr() as usize;
+            let mut records = self.lock_records();
+            if new_size == 0 {
+                records.retain(|r| r.offset != offset);
+            } else if let Some(rec) = records.iter_mut().find(|r| r.offset == offset) {
+                rec.size = new_size;
+            }
+        }
+        true
+    }
+
+    /// Lock the whole region into physical memory (`mlock` / `VirtualLock`).
+    ///
+    /// Locked pages are never written to swap, so data wiped from the arena
+    /// cannot persist on disk. Fails if the OS locked-memory limit is exceeded.
+    #[cfg(feature = "std")]
+    pub fn lock_pages(&self) -> Result<(), AllocFailed> {
+        #[cfg(target_os = "windows")]
+        if !self.commit_to(self.limit.as_ptr() as usize) {
+            return Err(AllocFailed::new(self.region_len()));
+        }
+        // Safety: the arena owns its whole region.
+        unsafe { sys::lock(self.base.as_ptr(), self.region_len())? };
+        self.locked.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// Unlock a region previously locked with [`lock_pages`](Self::lock_pages).
+    #[cfg(feature = "std")]
+    pub fn unlock_pages(&self) -> Result<(), AllocFailed> {
+        if self.locked.swap(false, Ordering::AcqRel) {
+            // Safety: the arena owns its whole region.
+            unsafe { sys::unlock(self.base.as_ptr(), self.region_len())? };
+        }
+        Ok(())
+    }
+
+    /// Touch every free page of the region so the OS backs it right away.
+    ///
+    /// Writes a zero at page stride from the cursor to the limit, turning
+    /// first-touch page faults into a one-time warmup cost. Only free memory
+    /// is written, so live allocations are untouched; RSS grows to the full
+    /// region size.
+    ///
+    /// # Safety
+    /// No allocation may run concurrently with this call.
+    pub unsafe fn prefault(&self) {
+        let end = self.limit.as_ptr() as usize;
+        #[cfg(all(feature = "std", target_os = "windows"))]
+        if !self.commit_to(end) {
+            return;
+        }
+        let cursor = self.cursor.load(Ordering::SeqCst);
+        let mut addr = self.mirror(cursor, end - cursor);
+        let end = addr + (end - cursor);
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(addr, end - addr);
+        #[cfg(feature = "sanitizer")]
+        let start = addr;
+        #[cfg(feature = "std")]
+        let page = sys::page_size();
+        #[cfg(not(feature = "std"))]
+        let page = PAGE_ALIGN;
+        while addr < end {
+            core::ptr::write_volatile(addr as *mut u8, 0);
+            addr = (addr + page) & !(page - 1);
+        }
+        #[cfg(feature = "sanitizer")]
+        asan::poison(start, end - start);
+    }
+
+    /// Commit the region lazily: only [`COMMIT_CHUNK`]-sized steps up to the
+    /// cursor are committed, as allocation advances.
+    ///
+    /// # Safety
+    /// The region must come from [`sys::reserve`] and no part of it may be
+    /// allocated yet.
+    #[cfg(all(feature = "std", target_os = "windows"))]
+    pub(crate) unsafe fn set_commit_on_demand(&self) {
+        self.committed
+            .store(self.base.as_ptr() as usize, Ordering::Release);
+    }
+
+    /// Move the cursor of a fresh arena to `used` bytes past the base,
+    /// e.g. to resume a region whose contents were persisted.
+    ///
+    /// # Safety
+    /// `used` must not exceed the region, and nothing may have been
+    /// allocated from the arena yet.
+    #[cfg(all(feature = "std", unix))]
+    pub(crate) unsafe fn resume_at(&self, used: usize) {
+        debug_assert!(used <= self.region_len());
+        let cursor = self.base.as_ptr() as usize + used;
+        self.cursor.store(cursor, Ordering::SeqCst);
+        self.dirty_end.fetch_max(cursor, Ordering::Relaxed);
+        self.peak.fetch_max(used, Ordering::Relaxed);
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(self.base.as_ptr() as usize, used);
+        // Bytes past the restored cursor may hold data from a previous run.
+        self.mark_recycled();
+    }
+
+    /// Make sure `[base, end)` is committed, committing the next
+    /// [`COMMIT_CHUNK`] step if needed. Returns `false` if the OS refuses.
+    #[cfg(all(feature = "std", target_os = "windows"))]
+    #[inline(always)]
+    fn commit_to(&self, end: usize) -> bool {
+        end <= self.committed.load(Ordering::Acquire) || self.commit_slow(end)
+    }
+
+    #[cfg(all(feature = "std", target_os = "windows"))]
+    #[cold]
+    fn commit_slow(&self, end: usize) -> bool {
+        let base = self.base.as_ptr() as usize;
+        let limit = self.limit.as_ptr() as usize;
+        let committed = self.committed.load(Ordering::Acquire);
+        if end <= committed {
+            return true;
+        }
+        let target = base
+            .saturating_add((end - base).div_ceil(COMMIT_CHUNK) * COMMIT_CHUNK)
+            .min(limit);
+        // Concurrent callers may commit overlapping ranges; that is harmless.
+        // Safety: a partly committed region was reserved with `sys::reserve`.
+        if unsafe { sys::commit(committed as *mut u8, target - committed) }.is_err() {
+            return false;
+        }
+        self.committed.fetch_max(target, Ordering::AcqRel);
+        true
+    }
+
+    /// Request transparent huge pages for the region (`MADV_HUGEPAGE`).
+    ///
+    /// A lighter alternative to explicit huge pages that needs no admin
+    /// setup; the kernel promotes the region to 2MB pages when it can.
+    /// A no-op on platforms without transparent huge pages. The region must
+    /// be page-aligned.
+    #[cfg(feature = "std")]
+    pub fn advise_huge(&self) -> Result<(), AllocFailed> {
+        sys::advise_huge(self.base.as_ptr(), self.region_len())
+    }
+
+    /// Make the allocated part of the region read-only.
+    ///
+    /// Protects `[base, cursor)`, rounded up to whole pages, so any write to
+    /// it faults at the offending instruction. The region must be
+    /// page-aligned (as every OS-backed arena is). The rest of the last
+    /// page is frozen too, so the cursor skips to the next page: blocks
+    /// allocated while frozen are writable, and the skipped bytes count as
+    /// used until the next reset. Call [`unfreeze`](Self::unfreeze) before
+    /// resetting. Chained chunks of a growable arena are not frozen.
+    #[cfg(feature = "std")]
+    pub fn freeze(&self) -> Result<(), AllocFailed> {
+        let base = self.base.as_ptr() as usize;
+        let page = sys::page_size();
+        let frozen_len = |cursor: usize| {
+            let used = (cursor - base).min(self.region_len());
+            match self.direction {
+                GrowDirection::Up => used.div_ceil(page) * page,
+                GrowDirection::Down if used == 0 => 0,
+                GrowDirection::Down => {
+                    let limit = self.limit.as_ptr() as usize;
+                    limit - ((limit - used) & !(page - 1))
+                }
+            }
+        };
+        let previous = self
+            .cursor
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |cursor| {
+                Some(cursor.max(base + frozen_len(cursor)))
+            })
+            .unwrap_or_else(|cursor| cursor);
+        let len = frozen_len(previous);
+        self.peak.fetch_max(len, Ordering::Relaxed);
+        self.unfreeze()?;
+        if len > 0 {
+            // Safety: the pages lie within the region; a later write to
+            // them faults instead of going through.
+            unsafe { sys::protect(self.frozen_start(len), len, Protection::Read)? };
+            self.frozen_len.store(len, Ordering::Release);
+        }
+        Ok(())
+    }
+
+    /// Make a region frozen with [`freeze`](Self::freeze) writable again.
+    #[cfg(feature = "std")]
+    pub fn unfreeze(&self) -> Result<(), AllocFailed> {
+        let len = self.frozen_len.swap(0, Ordering::AcqRel);
+        if len > 0 {
+            // Safety: these pages were frozen by `freeze`, within the region.
+            let result =
+                unsafe { sys::protect(self.frozen_start(len), len, Protection::ReadWrite) };
+            if let Err(err) = result {
+                self.frozen_len.store(len, Ordering::Release);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Start of a `len`-byte frozen range: the pages nearest the base, or
+    /// the limit for an arena growing down.
+    #[cfg(feature = "std")]
+    fn frozen_start(&self, len: usize) -> *mut u8 {
+        match self.direction {
+            GrowDirection::Up => self.base.as_ptr(),
+            GrowDirection::Down => self.limit.as_ptr().wrapping_sub(len),
+        }
+    }
+
+    /// Check if part of the region is frozen read-only.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_len.load(Ordering::Acquire) > 0
+    }
+
+    /// Check if the region is locked into physical memory.
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::Acquire)
+    }
+
+    /// Enable record mode.
+    ///
+    /// While enabled, every successful allocation is recorded as an
+    /// [`AllocRecord`]. Records are cleared on reset. Record mode is meant
+    /// for arenas used directly: the record list itself is heap-allocated,
+    /// so it must not be enabled on an arena that backs the global allocator.
+    #[inline]
+    pub fn enable_recording(&self) {
+        self.recording.store(true, Ordering::Release);
+    }
+
+    /// Disable record mode and discard all records.
+    #[inline]
+    pub fn disable_recording(&self) {
+        self.recording.store(false, Ordering::Release);
+        self.lock_records().clear();
+    }
+
+    /// Check if record mode is enabled.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.recording.load(Ordering::Acquire)
+    }
+
+    /// Iterate over the live allocations captured in record mode, in
+    /// address order.
+    ///
+    /// Iterates over a snapshot, so the arena may be used meanwhile. Empty
+    /// unless [`enable_recording`](Self::enable_recording) was called; only
+    /// allocations made since then and not released by a reset, rewind or
+    /// [`dealloc_last`](Self::dealloc_last) are listed.
+    pub fn iter_allocations(&self) -> impl Iterator<Item = AllocRecord> {
+        let mut records = self.lock_records().clone();
+        records.sort_unstable_by_key(|r| r.offset);
+        records.into_iter()
+    }
+
+    /// Check the arena's bookkeeping for consistency.
+    ///
+    /// Verifies that the cursor lies within the region and below the peak
+    /// and, in record mode, that every recorded allocation is aligned,
+    /// lies below the cursor and overlaps no other. Meant for test
+    /// harnesses and fuzz targets; takes the record lock and sorts the
+    /// records. With the `verify-invariants` feature every allocation is
+    /// also checked as it is made, panicking on the first unsound block.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        let base = self.base.as_ptr() as usize;
+        let cursor = self.cursor.load(Ordering::SeqCst);
+        if cursor < base || cursor > self.limit.as_ptr() as usize {
+            return Err(InvariantError::CursorOutOfBounds {
+                offset: cursor.wrapping_sub(base),
+            });
+        }
+        let used = cursor - base;
+        let peak = self.peak.load(Ordering::Relaxed);
+        if peak < used {
+            return Err(InvariantError::PeakBelowUsed { peak, used });
+        }
+
+        let mut records = self.lock_records().clone();
+        records.sort_unstable_by_key(|r| r.offset);
+        for r in &records {
+            if (base + r.offset) % r.align != r.phase {
+                return Err(InvariantError::Misaligned(*r));
+            }
+            if self.mirror(base + r.offset, r.size) + r.size > cursor {
+                return Err(InvariantError::PastCursor(*r));
+            }
+        }
+        for pair in records.windows(2) {
+            if pair[0].offset + pair[0].size > pair[1].offset {
+                return Err(InvariantError::Overlap(pair[0], pair[1]));
+            }
+        }
+        Ok(())
+    }
+
+    #[cold]
+    fn record(&self, addr: usize, size: usize, align: usize, phase: usize) {
+        self.lock_records().push(AllocRecord {
+            offset: addr - self.base.as_ptr() as usize,
+            size,
+            align,
+            phase,
+        });
+    }
+
+    #[cold]
+    fn capture_exhaustion(&self, size: usize, align: usize) {
+        let mut largest = self.lock_records().clone();
+        largest.sort_unstable_by_key(|r| core::cmp::Reverse(r.size));
+        largest.truncate(EXHAUSTION_CONTEXT_TOP);
+
+        let context = ExhaustionContext {
+            requested_size: size,
+            requested_align: align,
+            remaining: self.remaining(),
+            largest,
+        };
+        *self.last_exhaustion.lock() = Some(context);
+    }
+
+    /// Get the context of the most recent allocation failure.
+    ///
+    /// Only populated when an allocation fails while record mode is enabled.
+    pub fn last_exhaustion_context(&self) -> Option<ExhaustionContext> {
+        self.last_exhaustion.lock().clone()
+    }
+
+    fn lock_records(&self) -> MutexGuard<'_, Vec<AllocRecord>> {
+        self.records.lock()
+    }
+
+    /// Tag `[ptr, ptr+size)` as secret: it must be securely wiped before
+    /// the arena is reset or released.
+    ///
+    /// A block a growable arena placed in a chained chunk is recorded
+    /// against that chunk, at its offset from the chunk's base.
+    #[cfg(all(debug_assertions, feature = "std"))]
+    pub(crate) fn taint(&self, ptr: *mut u8, size: usize, align: usize) {
+        let addr = ptr as usize;
+        #[cfg(feature = "growable")]
+        if !(self.base.as_ptr() as usize..self.limit.as_ptr() as usize).contains(&addr) {
+            self.for_each_chunk(|chunk| {
+                let (base, limit) = chunk.region();
+                if (base..limit).contains(&addr) {
+                    chunk.tainted.lock().push(AllocRecord {
+                        offset: addr - base,
+                        size,
+                        align,
+                        phase: 0,
+                    });
+                }
+            });
+            return;
+        }
+        self.tainted.lock().push(AllocRecord {
+            offset: addr - self.base.as_ptr() as usize,
+            size,
+            align,
+            phase: 0,
+        });
+    }
+
+    /// Panic if a tainted region at or past `offset` of the initial region
+    /// was never wiped, as `action` is about to discard it.
+    ///
+    /// `offset` counts from the base in cursor space, like [`used`](Self::used),
+    /// while taints record their address; the two differ for an arena
+    /// growing down.
+    #[cfg(debug_assertions)]
+    #[track_caller]
+    pub(crate) fn assert_wiped_from(&self, offset: usize, action: &str) {
+        let base = self.base.as_ptr() as usize;
+        let discarded = |r: &&AllocRecord| self.mirror(base + r.offset, r.size) - base >= offset;
+        if let Some(leak) = self.tainted.lock().iter().find(discarded) {
+            self.report_unwiped(action, leak, "");
+        }
+    }
+
+    /// Panic if any tainted region, in the initial region or a chained
+    /// chunk, was never wiped, as `action` is about to discard them all.
+    #[cfg(debug_assertions)]
+    #[track_caller]
+    pub(crate) fn assert_wiped(&self, action: &str) {
+        self.assert_wiped_from(0, action);
+        #[cfg(feature = "growable")]
+        self.for_each_chunk(|chunk| {
+            if let Some(leak) = chunk.tainted.lock().first() {
+                self.report_unwiped(action, leak, " of a chained chunk");
+            }
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    #[track_caller]
+    fn report_unwiped(&self, action: &str, leak: &AllocRecord, place: &str) {
+        #[cfg(feature = "std")]
+        if std::thread::panicking() {
+            return;
+        }
+        panic!(
+            "{} discards {} secret bytes at offset {}{} of arena '{}' that were never securely wiped",
+            action,
+            leak.size,
+            leak.offset,
+            place,
+            self.label()
+        );
+    }
+
+    /// Check if this arena has been recycled (reset after initial use).
+    #[inline]
+    pub fn is_recycled(&self) -> bool {
+        self.is_recycled.load(Ordering::Relaxed)
+    }
+
+    /// Reset the bump pointer to the base.
+    ///
+    /// With the `poison` feature, the used region is first overwritten with
+    /// [`POISON_WORD`](crate::config::POISON_WORD), so reads after reset
+    /// return obvious garbage instead of stale data.
+    ///
+    /// # Safety
+    /// All previously allocated memory becomes invalid after this call.
+    #[inline]
+    pub unsafe fn reset(&self) {
+        #[cfg(debug_assertions)]
+        self.assert_wiped("reset");
+        #[cfg(feature = "poison")]
+        self.poison_used();
+        self.reset_cursor();
+    }
+
+    /// Fill the used region with the poison word.
+    #[cfg(feature = "poison")]
+    unsafe fn poison_used(&self) {
+        let bytes = crate::config::POISON_WORD.to_ne_bytes();
+        let used = self.cursor.load(Ordering::SeqCst) - self.base.as_ptr() as usize;
+        let start = self.mirror(self.base.as_ptr() as usize, used);
+        let end = start + used;
+        // Padding between blocks is still poisoned for ASan.
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(start, used);
+        // Phase by address so aligned words read back as the poison word.
+        for addr in start..end {
+            *(addr as *mut u8) = bytes[addr % bytes.len()];
+        }
+    }
+
+    /// Reset the bump pointer without poisoning.
+    unsafe fn reset_cursor(&self) {
+        if self.is_recording() {
+            self.lock_records().clear();
+        }
+        self.rewind_cursor(self.base.as_ptr() as usize);
+    }
+
+    /// Move the cursor back to `to` as a reset, discarding everything past
+    /// it and every chained chunk.
+    unsafe fn rewind_cursor(&self, to: usize) {
+        let previous = self.cursor.swap(to, Ordering::SeqCst);
+        self.resets.fetch_add(1, Ordering::AcqRel);
+        self.dirty_end.fetch_max(previous, Ordering::Relaxed);
+        #[cfg(feature = "sanitizer")]
+        self.poison_free();
+        self.mark_recycled();
+        #[cfg(feature = "std")]
+        if self.release_on_reset.load(Ordering::Relaxed) {
+            let _ = self.release_pages();
+        }
+        #[cfg(feature = "growable")]
+        self.free_chunks();
+    }
+
+    /// Return the physical pages past the cursor to the OS.
+    ///
+    /// Issues `madvise(MADV_DONTNEED)` on Linux, `MADV_FREE` on macOS/BSD,
+    /// and a decommit/recommit on Windows over every whole page between the
+    /// cursor and the limit. The mapping stays reserved, so the arena keeps
+    /// working; released pages just fault in again on next use. Right after
+    /// a reset this drops the process RSS back to baseline.
+    ///
+    /// # Safety
+    /// No allocation may run concurrently with this call.
+    #[cfg(feature = "std")]
+    pub unsafe fn release_pages(&self) -> Result<(), AllocFailed> {
+        self.trim_to(0)
+    }
+
+    /// Return the physical pages more than `bytes` past the base to the OS.
+    ///
+    /// Like [`release_pages`](Self::release_pages), but keeps the first
+    /// `bytes` of the region resident, e.g. after one unusually large proof:
+    /// trimming to the [`peak_used`](Self::peak_used) of typical workloads
+    /// (measured after a [`reset_stats`](Self::reset_stats)) shrinks the RSS
+    /// without making every following proof fault its pages in again.
+    /// Allocated memory is never released: the trim starts at the cursor at
+    /// the earliest. The virtual reservation is kept.
+    ///
+    /// # Safety
+    /// No allocation may run concurrently with this call.
+    #[cfg(feature = "std")]
+    pub unsafe fn trim_to(&self, bytes: usize) -> Result<(), AllocFailed> {
+        let page = sys::page_size();
+        let keep = (self.base.as_ptr() as usize).saturating_add(bytes);
+        let start = self.cursor.load(Ordering::SeqCst).max(keep);
+        let limit = self.limit.as_ptr() as usize;
+        // For an arena growing down, the free pages lie below the mirror
+        // of `start`.
+        let (start, end) = match self.direction {
+            GrowDirection::Up => (start, limit),
+            GrowDirection::Down => (
+                self.base.as_ptr() as usize,
+                self.mirror(start.min(limit), 0),
+            ),
+        };
+        let start = start.saturating_add(page - 1) & !(page - 1);
+        let end = end & !(page - 1);
+        if start >= end {
+            return Ok(());
+        }
+        sys::release(start as *mut u8, end - start)
+    }
+
+    /// Choose whether [`reset`](Self::reset) also calls [`release_pages`](Self::release_pages).
+    ///
+    /// Releasing keeps RSS low for long-running services at the cost of
+    /// page faults when the memory is used again.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn set_release_on_reset(&self, enabled: bool) {
+        self.release_on_reset.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Reset the arena, but keep the `n` most recent allocations.
+    ///
+    /// The kept allocations are moved down to the base of the arena (in
+    /// their original order, with their original alignment and phase) and
+    /// the cursor is placed just past them. Returns where each kept
+    /// allocation moved. Everything else is discarded as by
+    /// [`reset`](Self::reset), including chained chunks, whose allocations
+    /// are never recorded and so never kept.
+    ///
+    /// Requires record mode; returns [`BumpError::RecordingDisabled`] otherwise.
+    /// Fails with [`BumpError::GrowsDown`] for an arena growing down.
+    ///
+    /// # Safety
+    /// All previously allocated memory becomes invalid after this call,
+    /// except the kept allocations at their new addresses.
+    pub unsafe fn reset_keeping_last(&self, n: usize) -> Result<Vec<Relocation>, BumpError> {
+        if self.direction == GrowDirection::Down {
+            return Err(BumpError::GrowsDown);
+        }
+        if !self.is_recording() {
+            return Err(BumpError::RecordingDisabled);
+        }
+        #[cfg(debug_assertions)]
+        self.assert_wiped("reset_keeping_last");
+
+        let mut records = self.lock_records();
+        records.sort_unstable_by_key(|r| r.offset);
+        let keep_from = records.len().saturating_sub(n);
+        let kept: Vec<AllocRecord> = records.drain(keep_from..).collect();
+        records.clear();
+
+        // Moving in ascending order is safe: each allocation only ever moves
+        // down, so `copy` (memmove) never clobbers one that is yet to move.
+        let base = self.base.as_ptr() as usize;
+        let mut cursor = base;
+        let mut relocations = Vec::with_capacity(kept.len());
+        for rec in kept {
+            let old = (base + rec.offset) as *mut u8;
+            // The lowest address at or past the cursor with the same phase,
+            // e.g. the cache color of the block.
+            let new_addr = ((cursor - rec.phase + rec.align - 1) & !(rec.align - 1)) + rec.phase;
+            let new = new_addr as *mut u8;
+            #[cfg(feature = "sanitizer")]
+            asan::unpoison(new_addr, rec.size);
+            core::ptr::copy(old, new, rec.size);
+            cursor = new_addr + rec.size;
+
+            records.push(AllocRecord {
+                offset: new_addr - base,
+                ..rec
+            });
+            relocations.push(Relocation {
+                old,
+                new,
+                size: rec.size,
+            });
+        }
+
+        drop(records);
+        self.rewind_cursor(cursor);
+        Ok(relocations)
+    }
+
+    /// Snapshot the current cursor position.
+    ///
+    /// Pass the returned marker to [`restore`](Self::restore) to reclaim
+    /// everything allocated after this point without a full reset.
+    #[inline]
+    pub fn checkpoint(&self) -> Marker {
+        Marker {
+            resets: self.resets.load(Ordering::Acquire),
+            cursor: self.cursor.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Returns the arena's generation: the number of resets so far.
+    ///
+    /// Every reset, secure or not, increments it; [`restore`](Self::restore)
+    /// does not. A pointer allocated in an older generation has been
+    /// invalidated, which [`ArenaBox`](crate::boxed::ArenaBox) checks in
+    /// debug builds.
+    #[inline]
+    pub fn generation(&self) -> usize {
+        self.resets.load(Ordering::Acquire)
+    }
+
+    /// Check whether [`restore`](Self::restore) would accept `marker` now.
+    #[inline]
+    pub fn check_marker(&self, marker: Marker) -> Result<(), BumpError> {
+        if marker.cursor > self.cursor.load(Ordering::SeqCst) {
+            Err(BumpError::MarkerAhead)
+        } else if marker.resets != self.resets.load(Ordering::Acquire) {
+            Err(BumpError::StaleMarker)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Rewind the cursor back to a previously taken checkpoint.
+    ///
+    /// Returns [`BumpError::MarkerAhead`] if the arena has not advanced past
+    /// the marker (for example, because it was reset since the checkpoint),
+    /// and [`BumpError::StaleMarker`] if it was reset since the checkpoint
+    /// and has advanced past it again.
+    ///
+    /// # Safety
+    /// All memory allocated after the checkpoint becomes invalid.
+    #[inline]
+    pub unsafe fn restore(&self, marker: Marker) -> Result<(), BumpError> {
+        debug_assert!(marker.cursor >= self.base.as_ptr() as usize);
+        debug_assert!(marker.cursor <= self.limit.as_ptr() as usize);
+
+        self.check_marker(marker)?;
+        #[cfg(debug_assertions)]
+        self.assert_wiped_from(marker.cursor - self.base.as_ptr() as usize, "restore");
+        let previous = self
+            .cursor
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                (marker.cursor <= current).then_some(marker.cursor)
+            })
+            .map_err(|_| BumpError::MarkerAhead)?;
+        self.dirty_end.fetch_max(previous, Ordering::Relaxed);
+        if previous > marker.cursor {
+            // Rewound memory is handed out again and may hold old data.
+            self.mark_recycled();
+            #[cfg(feature = "sanitizer")]
+            self.poison_free();
+        }
+
+        if self.is_recording() {
+            let base = self.base.as_ptr() as usize;
+            self.lock_records()
+                .retain(|r| self.mirror(base + r.offset, r.size) < marker.cursor);
+        }
+        Ok(())
+    }
+
+    /// Rewind the cursor to `offset` bytes from the base, keeping the first
+    /// `offset` bytes of allocations and discarding the rest.
+    ///
+    /// `offset` counts like [`used`](Self::used), so passing the value
+    /// `used` returned after building long-lived data preserves exactly
+    /// that data. Unlike [`restore`](Self::restore), no marker is needed:
+    /// any offset up to the current cursor is accepted, past it the call
+    /// fails with [`BumpError::OffsetAhead`].
+    ///
+    /// Only the initial region can be kept: bytes in chunks chained by a
+    /// growable arena are not preserved, and an `offset` reaching into them
+    /// also fails with [`BumpError::OffsetAhead`].
+    ///
+    /// # Safety
+    /// All memory allocated past `offset` becomes invalid.
+    #[inline]
+    pub unsafe fn reset_to(&self, offset: usize) -> Result<(), BumpError> {
+        if offset > self.region_len() {
+            return Err(BumpError::OffsetAhead);
+        }
+        let marker = Marker {
+            cursor: self.base.as_ptr() as usize + offset,
+            resets: self.resets.load(Ordering::Acquire),
+        };
+        match self.restore(marker) {
+            Err(BumpError::MarkerAhead) => Err(BumpError::OffsetAhead),
+            result => result,
+        }
+    }
+
+    /// Open a scope whose allocations are reclaimed when the guard drops.
+    ///
+    /// Captures a [`checkpoint`](Self::checkpoint) now and
+    /// [`restore`](Self::restore)s it when the returned guard is dropped,
+    /// including on early return or panic.
+    ///
+    /// # Safety
+    /// All memory allocated from this arena while the guard is alive
+    /// (through the guard or not) becomes invalid when it drops.
+    #[inline]
+    pub unsafe fn scope(&self) -> ScopeGuard<'_> {
+        ScopeGuard {
+            alloc: self,
+            marker: self.checkpoint(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Borrow the arena exclusively for a single-threaded burst of
+    /// allocations.
+    ///
+    /// The returned [`ExclusiveBump`] bumps a local cursor with no atomic
+    /// operations and hands it back when dropped; afterwards the arena
+    /// continues from there on the usual lock-free path.
+    #[inline]
+    pub fn take_exclusive(&mut self) -> ExclusiveBump<'_> {
+        let cursor = *self.cursor.get_mut();
+        ExclusiveBump {
+            alloc: self,
+            cursor,
+            allocs: 0,
+            padding: 0,
+            last_padding: 0,
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Zero out all memory handed out by the arena and reset the cursor.
+    ///
+    /// This is critical for security-sensitive applications like ZK provers,
+    /// where witness data must be wiped after use to prevent leakage.
+    ///
+    /// Only `[base, cursor)` is wiped, extended to the furthest point the
+    /// arena was rewound from by [`reset`](Self::reset),
+    /// [`restore`](Self::restore) or
+    /// [`reset_keeping_last`](Self::reset_keeping_last) since the last
+    /// secure reset. Memory past that was never handed out and holds no
+    /// secrets, so wiping stays cheap when little of a large arena is used.
+    ///
+    /// Uses volatile writes to prevent the compiler from optimizing away
+    /// the zeroing operation (dead store elimination).
+    ///
+    /// # Safety
+    /// All previously allocated memory becomes invalid after this call.
+    #[inline]
+    pub unsafe fn secure_reset(&self) {
+        // Use volatile writes to prevent dead store elimination.
+        // This ensures the memory is actually zeroed even if it's never read again.
+        self.for_each_wipe_region(|ptr, len| Self::volatile_memset(ptr, SECURE_WIPE_PATTERN, len));
+
+        // Compiler fence to ensure the wipe completes before any subsequent operations.
+        compiler_fence(Ordering::SeqCst);
+
+        self.finish_secure_reset();
+    }
+
+    /// Apply `f` to every region [`secure_reset`](Self::secure_reset) must wipe.
+    pub(crate) fn for_each_wipe_region(&self, mut f: impl FnMut(*mut u8, usize)) {
+        let base = self.base.as_ptr() as usize;
+        let end = self
+            .cursor
+            .load(Ordering::SeqCst)
+            .max(self.dirty_end.load(Ordering::Relaxed));
+        let start = self.mirror(base, end - base);
+        // Freed memory and padding are poisoned; the reset after the wipe
+        // poisons them again.
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(start, end - base);
+        f(start as *mut u8, end - base);
+
+        // Chained chunks are unmapped by `reset`, but wipe them first so the
+        // data never reaches the OS.
+        #[cfg(feature = "growable")]
+        self.for_each_chunk(|chunk| {
+            #[cfg(feature = "sanitizer")]
+            asan::unpoison(chunk.base.as_ptr() as usize, chunk.region_used());
+            f(chunk.base.as_ptr(), chunk.region_used())
+        });
+    }
+
+    /// Reset the cursor after every wipe region has been overwritten.
+    pub(crate) unsafe fn finish_secure_reset(&self) {
+        #[cfg(debug_assertions)]
+        self.tainted.lock().clear();
+        self.reset_cursor();
+        self.dirty_end
+            .store(self.base.as_ptr() as usize, Ordering::Relaxed);
+    }
+
+    /// Volatile memset implementation that cannot be optimized away.
+    ///
+    /// This is critical for cryptographic security - we need to guarantee
+    /// that sensitive data is actually erased from memory.
+    #[inline(never)]
+    pub(crate) unsafe fn volatile_memset(ptr: *mut u8, value: u8, len: usize) {
+        // Method 1: Use platform-specific secure zeroing where available
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            // explicit_bzero is guaranteed not to be optimized away
+            extern "C" {
+                fn explicit_bzero(s: *mut libc::c_void, n: libc::size_t);
+            }
+            // explicit_bzero can only write zeros; other patterns take the
+            // volatile loop.
+            if value == 0 {
+                explicit_bzero(ptr as *mut libc::c_void, len);
+            } else {
+                Self::volatile_fill(ptr, value, len);
+            }
+        }
+
+        #[cfg(target_vendor = "apple")]
+        {
+            // memset_s is guaranteed not to be optimized away (C11)
+            extern "C" {
+                fn memset_s(
+                    s: *mut libc::c_void,
+                    smax: libc::size_t,
+                    c: libc::c_int,
+                    n: libc::size_t,
+                ) -> libc::c_int;
+            }
+            let _ = memset_s(ptr as *mut libc::c_void, len, value as libc::c_int, len);
+        }
+
+        #[cfg(windows)]
+        {
+            // RtlSecureZeroMemory is a header-only inline in winnt.h, not an
+            // exported symbol, so there is nothing to link against. It is a
+            // volatile store loop itself; use ours.
+            Self::volatile_fill(ptr, value, len);
+        }
+
+        // Fallback for everything else
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_vendor = "apple",
+            windows
+        )))]
+        {
+            Self::volatile_fill(ptr, value, len);
+        }
+    }
+
+    /// Volatile write loop (works everywhere, slightly slower).
+    ///
+    /// Stores a word at a time, with byte stores for the unaligned head
+    /// and tail.
+    #[cfg(not(target_vendor = "apple"))]
+    #[inline(never)]
+    unsafe fn volatile_fill(ptr: *mut u8, value: u8, len: usize) {
+        const WORD: usize = core::mem::size_of::<usize>();
+        let head = ptr.align_offset(WORD).min(len);
+        let words = (len - head) / WORD;
+        let word = usize::from_ne_bytes([value; WORD]);
+        for i in 0..head {
+            core::ptr::write_volatile(ptr.add(i), value);
+        }
+        let aligned = ptr.add(head) as *mut usize;
+        for i in 0..words {
+            core::ptr::write_volatile(aligned.add(i), word);
+        }
+        for i in head + words * WORD..len {
+            core::ptr::write_volatile(ptr.add(i), value);
+        }
+    }
+
+    /// Volatile fill with bytes drawn from `next`, eight per call.
+    #[cfg(feature = "std")]
+    #[inline(never)]
+    pub(crate) unsafe fn volatile_fill_random(
+        ptr: *mut u8,
+        len: usize,
+        next: &mut impl FnMut() -> u64,
+    ) {
+        for start in (0..len).step_by(8) {
+            let bytes = next().to_le_bytes();
+            for (i, &byte) in bytes.iter().enumerate().take(len - start) {
+                core::ptr::write_volatile(ptr.add(start + i), byte);
+            }
+        }
+    }
+
+    /// Returns the total capacity in bytes.
+    ///
+    /// For a growable arena this includes every chained chunk.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        #[cfg(feature = "growable")]
+        return self.region_len() + self.chunk_sum(Self::region_len);
+        #[cfg(not(feature = "growable"))]
+        self.region_len()
+    }
+
+    /// Returns the `[base, limit)` addresses of the initial region.
+    ///
+    /// For external range checks, or to tell a sanitizer which part of the
+    /// region is allocated: with upward growth that is the first
+    /// [`used`](Self::used) bytes. Chained chunks are not included.
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        (self.base.as_ptr() as usize, self.limit.as_ptr() as usize)
+    }
+
+    /// Returns the size of the initial region in bytes.
+    #[inline]
+    pub(crate) fn region_len(&self) -> usize {
+        self.limit.as_ptr() as usize - self.base.as_ptr() as usize
+    }
+
+    /// Check whether `ptr` points into memory managed by this arena.
+    ///
+    /// For a growable arena this includes every chained chunk.
+    #[inline]
+    pub fn contains(&self, ptr: *const u8) -> bool {
+        let addr = ptr as usize;
+        let in_region = addr >= self.base.as_ptr() as usize && addr < self.limit.as_ptr() as usize;
+        #[cfg(feature = "growable")]
+        if !in_region && !self.next.load(Ordering::Relaxed).is_null() {
+            return self.chunk_sum(|chunk| chunk.contains(ptr) as usize) > 0;
+        }
+        in_region
+    }
+
+    /// Restart the statistics without touching allocated memory.
+    ///
+    /// Zeroes the allocation, failure, alignment, color and contention
+    /// counters and lowers the peak to the current usage, e.g. at the start
+    /// of a benchmark iteration. Safe to call while allocations are in
+    /// flight; those may be counted either before or after the reset.
+    pub fn reset_stats(&self) {
+        self.alloc_count.store(0, Ordering::Relaxed);
+        self.alloc_fail_count.store(0, Ordering::Relaxed);
+        self.align_reject_count.store(0, Ordering::Relaxed);
+        self.cas_retries.store(0, Ordering::Relaxed);
+        self.alignment_waste.store(0, Ordering::Relaxed);
+        self.color_waste.store(0, Ordering::Relaxed);
+        self.last_padding.store(0, Ordering::Relaxed);
+        #[cfg(feature = "stats-histogram")]
+        for class in &self.histogram {
+            class.store(0, Ordering::Relaxed);
+        }
+        self.peak.store(self.used(), Ordering::Relaxed);
+    }
+
+    /// Returns the number of bytes currently allocated.
+    ///
+    /// For a growable arena this includes every chained chunk.
+    #[inline]
+    pub fn used(&self) -> usize {
+        #[cfg(feature = "growable")]
+        return self.region_used() + self.chunk_sum(Self::region_used);
+        #[cfg(not(feature = "growable"))]
+        self.region_used()
+    }
+
+    /// Bytes used in the initial region alone, without chained chunks.
+    #[inline]
+    fn region_used(&self) -> usize {
+        (self.cursor.load(Ordering::Relaxed) - self.base.as_ptr() as usize).min(self.region_len())
+    }
+
+    /// Returns the highest number of bytes ever in use at once.
+    ///
+    /// Unlike [`used`](Self::used), this is preserved across resets, so it
+    /// can be used to size arenas from a representative workload.
+    #[inline]
+    pub fn peak_used(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful allocations served.
+    ///
+    /// Preserved across resets, so it covers a whole session.
+    #[inline]
+    pub fn alloc_count(&self) -> usize {
+        self.alloc_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the largest alignment an allocation may request.
+    ///
+    /// This is the biggest power of two no larger than the initial region:
+    /// a stricter alignment could place at most one allocation, so such
+    /// requests return null without touching the cursor, and are counted in
+    /// [`align_reject_count`](Self::align_reject_count).
+    #[inline]
+    pub fn max_align(&self) -> usize {
+        self.max_align
+    }
+
+    /// Returns the largest power of two the region's base address is a
+    /// multiple of.
+    ///
+    /// Offsets from the base that are multiples of an alignment up to this
+    /// value are aligned in absolute terms too.
+    #[inline]
+    pub fn base_alignment(&self) -> usize {
+        1 << (self.base.as_ptr() as usize).trailing_zeros()
+    }
+
+    /// Returns the number of allocations rejected for exceeding
+    /// [`max_align`](Self::max_align).
+    #[inline]
+    pub fn align_reject_count(&self) -> usize {
+        self.align_reject_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times an allocation lost the race for the
+    /// cursor to another thread and had to retry.
+    ///
+    /// Preserved across resets. A high ratio to
+    /// [`alloc_count`](Self::alloc_count) means the arena is
+    /// contention-bound and worth sharding, e.g. with
+    /// [`ArenaManager::with_scratch_shards`](crate::ArenaManager::with_scratch_shards).
+    #[inline]
+    pub fn cas_retries(&self) -> usize {
+        self.cas_retries.load(Ordering::Relaxed)
+    }
+
+    /// Returns the alignment padding skipped by the most recent successful
+    /// allocation.
+    ///
+    /// With concurrent allocations this is whichever finished last.
+    #[inline]
+    pub fn last_padding(&self) -> usize {
+        self.last_padding.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total alignment padding skipped by allocations.
+    ///
+    /// Preserved across resets. A large value relative to
+    /// [`used`](Self::used) suggests requests are aligned beyond what the
+    /// cursor naturally sits at; see also [`base_alignment`](Self::base_alignment).
+    #[inline]
+    pub fn alignment_waste(&self) -> usize {
+        self.alignment_waste.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of successful allocations per size class.
+    ///
+    /// Entry `i` counts sizes in `(2^(i-1), 2^i]` (entry 0 is 1-byte
+    /// allocations); the last entry also counts anything larger.
+    /// Preserved across resets.
+    #[cfg(feature = "stats-histogram")]
+    pub fn size_histogram(&self) -> [usize; SIZE_CLASSES] {
+        core::array::from_fn(|i| self.histogram[i].load(Ordering::Relaxed))
+    }
+
+    /// Returns the number of allocations that failed due to exhaustion.
+    ///
+    /// Preserved across resets, so it covers a whole session.
+    #[inline]
+    pub fn alloc_fail_count(&self) -> usize {
+        self.alloc_fail_count.load(Ordering::Relaxed)
+    }
+
+    /// Returns the fraction of the capacity in use, from `0.0` to `1.0`.
+    ///
+    /// An arena with no capacity reports `1.0`, as it cannot fit anything.
+    #[inline]
+    pub fn utilization(&self) -> f64 {
+        let capacity = self.capacity();
+        if capacity == 0 {
+            return 1.0;
+        }
+        self.used() as f64 / capacity as f64
+    }
+
+    /// Returns the number of bytes remaining.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.capacity().saturating_sub(self.used())
+    }
+}
+
+/// Histogram index for an allocation of `size` bytes.
+#[cfg(feature = "stats-histogram")]
+#[inline(always)]
+fn size_class(size: usize) -> usize {
+    size.checked_next_power_of_two()
+        .map_or(SIZE_CLASSES - 1, |class| class.trailing_zeros() as usize)
+        .min(SIZE_CLASSES - 1)
+}
+
+// Safety: BumpAlloc can be shared across threads because:
+// - `base` and `limit` are never modified after construction
+// - `cursor` uses atomic operations for thread-safe updates
+// - `is_recycled`, `locked`, `frozen_len`, `release_on_reset` and `recording` use atomic operations
+// - `records`, `tainted`, `last_exhaustion` and `serial` are protected by mutexes
+// - `exhaustion_hook` is an atomic function pointer
+// - `growable` and `next` use atomic operations, and chunks are only
+//   unmapped by `reset`, which requires exclusive use
+unsafe impl Send for BumpAlloc {}
+unsafe impl Sync for BumpAlloc {}
+
+impl Drop for BumpAlloc {
+    fn drop(&mut self) {
+        // The memory goes back to its owner, or the OS, usable again.
+        #[cfg(feature = "sanitizer")]
+        asan::unpoison(self.base.as_ptr() as usize, self.region_len());
+        #[cfg(feature = "growable")]
+        unsafe {
+            self.free_chunks()
+        };
+        if self.owned {
+            let region = core::ptr::slice_from_raw_parts_mut(self.base.as_ptr(), self.region_len());
+            // Safety: `owned` is only set by `from_boxed`, from this exact slice.
+            drop(unsafe { Box::from_raw(region) });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PAGE_ALIGN;
+
+    #[test]
+    fn test_nonnull_safety() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        assert_eq!(alloc.capacity(), 1024);
+        assert_eq!(alloc.used(), 0);
+        assert_eq!(alloc.remaining(), 1024);
+        assert!(!alloc.is_recycled());
+    }
+
+    #[test]
+    fn test_recycled_flag() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        assert!(!alloc.is_recycled());
+
+        let _ = alloc.alloc(64, 8);
+        assert!(!alloc.is_recycled());
+
+        unsafe { alloc.reset() };
+        assert!(alloc.is_recycled());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    #[allow(clippy::needless_range_loop)]
+    fn test_secure_reset_zeroes_memory() {
+        let mut buffer = vec![0xFFu8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        // Allocate and write data
+        let ptr = alloc.alloc(512, 8);
+        assert!(!ptr.is_null());
+        unsafe {
+            std::ptr::write_bytes(ptr, 0xAB, 512);
+        }
+
+        // Secure reset
+        unsafe { alloc.secure_reset() };
+
+        // Verify memory is zeroed
+        for i in 0..512 {
+            assert_eq!(buffer[i], 0, "Byte {} not zeroed", i);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_secure_reset_skips_untouched_memory() {
+        let mut buffer = vec![0xFFu8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let ptr = alloc.alloc(100, 1);
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 100) };
+        unsafe { alloc.secure_reset() };
+
+        assert!(buffer[..100].iter().all(|&b| b == 0));
+        assert!(buffer[100..].iter().all(|&b| b == 0xFF));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_secure_reset_wipes_rewound_region() {
+        let mut buffer = vec![0xFFu8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        // A plain reset leaves the first round's data behind the cursor.
+        let ptr = alloc.alloc(600, 1);
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, 600) };
+        unsafe { alloc.reset() };
+
+        let _ = alloc.alloc(100, 1);
+        unsafe { alloc.secure_reset() };
+
+        assert!(buffer[..600].iter().all(|&b| b == 0));
+        assert!(buffer[600..].iter().all(|&b| b == 0xFF));
+    }
+
+    #[test]
+    fn test_peak_used_survives_reset() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(300, 1);
+        let _ = alloc.alloc(200, 1);
+        assert_eq!(alloc.peak_used(), 500);
+
+        unsafe { alloc.reset() };
+        assert_eq!(alloc.used(), 0);
+        assert_eq!(alloc.peak_used(), 500);
+
+        let _ = alloc.alloc(100, 1);
+        assert_eq!(alloc.peak_used(), 500);
+        let _ = alloc.alloc(600, 1);
+        assert_eq!(alloc.peak_used(), 700);
+    }
+
+    #[test]
+    fn test_alloc_counters_survive_reset() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(100, 8);
+        let _ = alloc.alloc(100, 8);
+        assert!(alloc.alloc(2048, 8).is_null());
+        assert_eq!(alloc.alloc_count(), 2);
+        assert_eq!(alloc.alloc_fail_count(), 1);
+
+        unsafe { alloc.reset() };
+        let _ = alloc.alloc(100, 8);
+        assert_eq!(alloc.alloc_count(), 3);
+        assert_eq!(alloc.alloc_fail_count(), 1);
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_chains_chunks() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.set_growable(true);
+
+        let first = alloc.alloc(1000, 8);
+        assert!(!first.is_null());
+        let grown = alloc.alloc(4096, 64);
+        assert!(!grown.is_null());
+        assert_eq!(grown as usize % 64, 0);
+        unsafe { std::ptr::write_bytes(grown, 0xAB, 4096) };
+
+        assert!(alloc.capacity() > 1024);
+        assert_eq!(alloc.used(), 1000 + 4096);
+        assert_eq!(alloc.alloc_fail_count(), 0);
+
+        unsafe { alloc.reset() };
+        assert_eq!(alloc.capacity(), 1024);
+        assert_eq!(alloc.used(), 0);
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_used_counts_each_chunk_once() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.set_growable(true);
+
+        let mut used = 0;
+        let mut chunks = 0;
+        while chunks < 3 {
+            assert!(!alloc.alloc(1024, 1).is_null());
+            used += 1024;
+            chunks = alloc.chunk_sum(|_| 1);
+        }
+        assert_eq!(alloc.used(), used);
+        assert_eq!(alloc.remaining(), alloc.capacity() - used);
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_reset_to_rejects_chunk_offsets() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.set_growable(true);
+
+        let _ = alloc.alloc(512, 1);
+        assert!(!alloc.alloc(1024, 1).is_null());
+        let used = alloc.used();
+        assert!(used > buffer.len());
+        assert_eq!(unsafe { alloc.reset_to(used) }, Err(BumpError::OffsetAhead));
+        assert_eq!(alloc.used(), used);
+
+        unsafe { alloc.reset_to(256).unwrap() };
+        assert!(!alloc.alloc(256, 1).is_null());
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_secure_reset_wipes_every_chunk() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.set_growable(true);
+
+        let mut blocks = Vec::new();
+        while alloc.chunk_sum(|_| 1) < 3 {
+            let ptr = alloc.alloc(1024, 1);
+            assert!(!ptr.is_null());
+            unsafe { std::ptr::write_bytes(ptr, 0xAB, 1024) };
+            blocks.push(ptr);
+        }
+
+        // Every wipe region stays within the chunk it belongs to.
+        let mut chunks = vec![alloc.region()];
+        alloc.for_each_chunk(|chunk| chunks.push(chunk.region()));
+        let mut regions = Vec::new();
+        alloc.for_each_wipe_region(|ptr, len| regions.push((ptr as usize, len)));
+        assert_eq!(regions.len(), chunks.len());
+        for (&(start, len), &(base, limit)) in regions.iter().zip(&chunks) {
+            assert_eq!(start, base);
+            assert!(start + len <= limit);
+        }
+
+        // Blocks in the chunks are wiped before the chunks are unmapped.
+        alloc.for_each_wipe_region(|ptr, len| unsafe {
+            BumpAlloc::volatile_memset(ptr, SECURE_WIPE_PATTERN, len)
+        });
+        for &ptr in &blocks {
+            let block = unsafe { std::slice::from_raw_parts(ptr, 1024) };
+            assert!(block.iter().all(|&b| b == SECURE_WIPE_PATTERN));
+        }
+        unsafe { alloc.secure_reset() };
+        assert_eq!(alloc.used(), 0);
+        assert_eq!(alloc.capacity(), 1024);
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let ptr = alloc.alloc(64, 8);
+        assert!(alloc.contains(ptr));
+        assert!(alloc.contains(buffer.as_ptr()));
+        assert!(!alloc.contains(unsafe { buffer.as_ptr().add(1024) }));
+        assert!(!alloc.contains(std::ptr::null()));
+
+        let (base, limit) = alloc.region();
+        assert_eq!(base, buffer.as_ptr() as usize);
+        assert_eq!(limit - base, alloc.capacity());
+    }
+
+    #[test]
+    fn test_volatile_memset_non_zero_pattern() {
+        let mut buffer = vec![0u8; 4096];
+        unsafe { BumpAlloc::volatile_memset(buffer.as_mut_ptr(), 0xAA, buffer.len()) };
+        assert!(buffer.iter().all(|&b| b == 0xAA));
+
+        unsafe { BumpAlloc::volatile_memset(buffer.as_mut_ptr(), 0, buffer.len()) };
+        assert!(buffer.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg(not(target_vendor = "apple"))]
+    fn test_volatile_fill_unaligned() {
+        let mut buffer = [0u8; 64];
+        unsafe { BumpAlloc::volatile_fill(buffer.as_mut_ptr().add(3), 0x5C, 49) };
+        assert!(buffer[..3].iter().all(|&b| b == 0));
+        assert!(buffer[3..52].iter().all(|&b| b == 0x5C));
+        assert!(buffer[52..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_overflowing_requests_return_null() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let _ = alloc.alloc(8, 8);
+
+        assert!(alloc.alloc(usize::MAX, 1).is_null());
+        assert!(alloc.alloc(usize::MAX - 8, 8).is_null());
+        assert!(alloc.alloc(1, 1 << (usize::BITS - 1)).is_null());
+        assert!(alloc
+            .alloc(usize::MAX / 2, 1 << (usize::BITS - 2))
+            .is_null());
+        assert_eq!(alloc.used(), 8);
+        // The huge alignments are turned away before reaching the cursor.
+        assert_eq!(alloc.alloc_fail_count(), 2);
+        assert_eq!(alloc.align_reject_count(), 2);
+    }
+
+    #[test]
+    fn test_grow_in_place() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let first = alloc.alloc(64, 8);
+        assert!(alloc.grow_in_place(first, 64, 128));
+        assert_eq!(alloc.used(), 128);
+
+        // Only the most recent allocation can grow.
+        let second = alloc.alloc(64, 8);
+        assert!(!alloc.grow_in_place(first, 128, 256));
+        assert!(alloc.grow_in_place(second, 64, 512));
+        assert_eq!(alloc.used(), 640);
+
+        // Growing past the limit fails without moving the cursor.
+        assert!(!alloc.grow_in_place(second, 512, 2048));
+        assert_eq!(alloc.used(), 640);
+    }
+
+    #[test]
+    fn test_grow_in_place_rejects_foreign_blocks() {
+        let mut buffer = vec![0u8; 1024];
+        let (low, high) = unsafe {
+            (
+                BumpAlloc::new(buffer.as_mut_ptr(), 512),
+                BumpAlloc::new(buffer.as_mut_ptr().add(512), 512),
+            )
+        };
+
+        // A block filling `low` ends where `high`'s cursor sits.
+        let block = low.alloc(512, 8);
+        assert!(!high.grow_in_place(block, 512, 1024));
+        assert_eq!(high.used(), 0);
+    }
+
+    #[test]
+    fn test_dealloc_last() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let first = alloc.alloc(64, 8);
+        let second = alloc.alloc(128, 8);
+
+        // Not the most recent allocation: no-op.
+        assert!(!unsafe { alloc.dealloc_last(first, 64) });
+        assert_eq!(alloc.used(), 192);
+
+        assert!(unsafe { alloc.dealloc_last(second, 128) });
+        assert!(unsafe { alloc.dealloc_last(first, 64) });
+        assert_eq!(alloc.used(), 0);
+        assert!(alloc.is_recycled());
+        assert_eq!(alloc.alloc(64, 8), first);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_prefault_leaves_live_allocations() {
+        let mut buffer = vec![0xAAu8; 3 * PAGE_ALIGN];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let ptr = alloc.alloc(100, 1);
+
+        unsafe { alloc.prefault() };
+
+        let live = unsafe { core::slice::from_raw_parts(ptr, 100) };
+        assert!(live.iter().all(|&b| b == 0xAA));
+        assert_eq!(buffer[100], 0);
+    }
+
+    #[test]
+    fn test_utilization() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(alloc.utilization(), 0.0);
+        alloc.alloc(256, 1);
+        assert_eq!(alloc.utilization(), 0.25);
+        alloc.alloc(768, 1);
+        assert_eq!(alloc.utilization(), 1.0);
+    }
+
+    #[cfg(feature = "poison")]
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_reset_poisons_used_memory() {
+        let mut buffer = vec![0u64; 128];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr() as *mut u8, 1024) };
+        let ptr = alloc.alloc(64, 8) as *mut u32;
+        unsafe {
+            ptr.write(7);
+            alloc.reset();
+            assert_eq!(ptr.read(), crate::config::POISON_WORD);
+            assert_eq!(ptr.add(15).read(), crate::config::POISON_WORD);
+            // Past the old cursor nothing was touched.
+            assert_eq!(ptr.add(16).read(), 0);
+
+            alloc.alloc(64, 8);
+            alloc.secure_reset();
+            assert_eq!(ptr.read(), 0);
+        }
+    }
+
+    #[test]
+    fn test_from_slice() {
+        let buf = Box::leak(vec![0u8; 1024].into_boxed_slice());
+        let alloc = BumpAlloc::from_slice(buf);
+        assert_eq!(alloc.capacity(), 1024);
+        assert!(!alloc.alloc(512, 8).is_null());
+    }
+
+    #[test]
+    fn test_from_boxed() {
+        let alloc = BumpAlloc::from_boxed(vec![0u8; 4096].into_boxed_slice());
+        assert_eq!(alloc.capacity(), 4096);
+        let ptr = alloc.alloc(4096, 1);
+        unsafe { core::ptr::write_bytes(ptr, 0xAB, 4096) };
+        assert!(alloc.alloc(1, 1).is_null());
+        // The buffer is freed when `alloc` drops.
+    }
+
+    #[test]
+    #[should_panic(expected = "must not be empty")]
+    fn test_from_boxed_rejects_empty() {
+        BumpAlloc::from_boxed(Box::new([]));
+    }
+
+    #[test]
+    fn test_alloc_zeroed() {
+        let mut buffer = vec![0xAAu8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let ptr = alloc.alloc_zeroed(128, 8);
+        assert!(unsafe { core::slice::from_raw_parts(ptr, 128) }
+            .iter()
+            .all(|&b| b == 0));
+
+        let mut zeroed = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new_zeroed(zeroed.as_mut_ptr(), zeroed.len()) };
+        let ptr = alloc.alloc_zeroed(128, 8);
+        unsafe { core::ptr::write_bytes(ptr, 0xFF, 128) };
+        unsafe { alloc.reset() };
+        let ptr = alloc.alloc_zeroed(128, 8);
+        assert!(unsafe { core::slice::from_raw_parts(ptr, 128) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_alignment_cap() {
+        let mut buffer = vec![0u8; 3000];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(alloc.max_align(), 2048);
+
+        assert!(alloc.alloc(8, 1 << (usize::BITS - 1)).is_null());
+        assert_eq!(alloc.align_reject_count(), 1);
+        assert_eq!(alloc.alloc_fail_count(), 0);
+        assert_eq!(alloc.used(), 0);
+
+        assert!(!alloc.alloc(8, 8).is_null());
+        assert_eq!(alloc.align_reject_count(), 1);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing_events() {
+        use std::sync::atomic::AtomicUsize;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata};
+
+        #[derive(Default)]
+        struct Fields {
+            arena: String,
+            size: u64,
+            success: bool,
+        }
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, _: &Field, _: &dyn fmt::Debug) {}
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if field.name() == "arena" {
+                    self.arena = value.to_string();
+                }
+            }
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                if field.name() == "size" {
+                    self.size = value;
+                }
+            }
+            fn record_bool(&mut self, field: &Field, value: bool) {
+                if field.name() == "success" {
+                    self.success = value;
+                }
+            }
+        }
+
+        static EVENTS: AtomicUsize = AtomicUsize::new(0);
+        static FAILED: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counter;
+        impl tracing::Subscriber for Counter {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &Attributes<'_>) -> Id {
+                Id::from_u64(1)
+            }
+            fn record(&self, _: &Id, _: &Record<'_>) {}
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                assert_eq!(fields.arena, "scratch");
+                assert_eq!(fields.size, 512);
+                EVENTS.fetch_add(1, Ordering::Relaxed);
+                if !fields.success {
+                    FAILED.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let mut buffer = vec![0u8; 1024];
+        let alloc =
+            unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) }.with_label("scratch");
+        tracing::subscriber::with_default(Counter, || {
+            alloc.alloc(512, 8);
+            alloc.alloc(512, 8);
+            alloc.alloc(512, 8);
+        });
+        assert_eq!(EVENTS.load(Ordering::Relaxed), 3);
+        assert_eq!(FAILED.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_exhaustion_hook() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static LAST_SIZE: AtomicUsize = AtomicUsize::new(0);
+        fn hook(size: usize, _align: usize, remaining: usize) {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            LAST_SIZE.store(size, Ordering::Relaxed);
+            assert_eq!(remaining, 1024);
+        }
+
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        assert!(alloc.alloc(2048, 8).is_null());
+        assert_eq!(CALLS.load(Ordering::Relaxed), 0);
+
+        alloc.set_exhaustion_hook(Some(hook));
+        assert!(alloc.alloc(4096, 8).is_null());
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+        assert_eq!(LAST_SIZE.load(Ordering::Relaxed), 4096);
+
+        alloc.set_exhaustion_hook(None);
+        assert!(alloc.alloc(4096, 8).is_null());
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_alloc_nn() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let ptr = alloc.alloc_nn(512, 8).expect("allocation should succeed");
+        assert_eq!(ptr.as_ptr(), buffer.as_mut_ptr());
+
+        assert!(alloc.alloc_nn(1024, 8).is_none());
+    }
+
+    #[test]
+    fn test_alloc_with_phase() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        for phase in [0, 8, 24, 56] {
+            let ptr = alloc.alloc_with_phase(16, 64, phase);
+            assert!(!ptr.is_null());
+            assert_eq!((ptr as usize) % 64, phase);
+        }
+    }
+
+    #[test]
+    fn test_exhaustion_context() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        // Without record mode nothing is captured
+        assert!(alloc.alloc(8192, 8).is_null());
+        assert!(alloc.last_exhaustion_context().is_none());
+
+        alloc.enable_recording();
+        for size in [64, 2048, 128, 1024, 256, 32] {
+            assert!(!alloc.alloc(size, 8).is_null());
+        }
+        assert!(alloc.alloc(1024, 8).is_null());
+
+        let context = alloc.last_exhaustion_context().unwrap();
+        assert_eq!(context.requested_size, 1024);
+        assert_eq!(context.requested_align, 8);
+        assert_eq!(context.remaining, alloc.remaining());
+        let sizes: Vec<usize> = context.largest.iter().map(|r| r.size).collect();
+        assert_eq!(sizes, vec![2048, 1024, 256, 128]);
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let long_lived = alloc.alloc(128, 8);
+        let marker = alloc.checkpoint();
+
+        let transient = alloc.alloc(512, 8);
+        assert!(!transient.is_null());
+        assert_eq!(alloc.used(), 640);
 
         assert!(!alloc.is_recycled());
+        unsafe { alloc.restore(marker).unwrap() };
+        assert_eq!(alloc.used(), 128);
+        assert!(alloc.is_recycled());
+
+        // The reclaimed space is handed out again
+        assert_eq!(alloc.alloc(512, 8), transient);
+        assert_eq!(long_lived, alloc.base_ptr());
+    }
+
+    #[test]
+    fn test_scope_guard_reclaims_on_drop() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
 
         let _ = alloc.alloc(64, 8);
-        assert!(!alloc.is_recycled());
+        {
+            let scope = unsafe { alloc.scope() };
+            assert!(!scope.alloc(256, 8).is_null());
+            assert!(!alloc.alloc(256, 8).is_null());
+            assert_eq!(alloc.used(), 576);
+        }
+        assert_eq!(alloc.used(), 64);
+
+        // Reclaimed on unwind as well
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let scope = unsafe { alloc.scope() };
+            let _ = scope.alloc(512, 8);
+            panic!("proof round failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(alloc.used(), 64);
+    }
+
+    #[test]
+    fn test_restore_rejects_marker_ahead() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
 
+        let _ = alloc.alloc(256, 8);
+        let marker = alloc.checkpoint();
         unsafe { alloc.reset() };
-        assert!(alloc.is_recycled());
+
+        assert_eq!(
+            unsafe { alloc.restore(marker) },
+            Err(BumpError::MarkerAhead)
+        );
+        assert_eq!(alloc.used(), 0);
     }
 
     #[test]
-    fn test_secure_reset_zeroes_memory() {
-        let mut buffer = vec![0xFFu8; 1024];
+    fn test_alloc_offset() {
+        let mut buffer = vec![0u8; 1024];
         let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
 
-        // Allocate and write data
-        let ptr = alloc.alloc(512, 8);
+        let (first, ptr) = alloc.alloc_offset(10, 1).unwrap();
+        assert_eq!(first, 0);
+        assert_eq!(ptr, alloc.base_ptr());
+        let (second, ptr) = alloc.alloc_offset(16, 8).unwrap();
+        assert!(second >= 10);
+        assert_eq!(ptr as usize % 8, 0);
+        assert_eq!(unsafe { alloc.base_ptr().add(second) }, ptr);
+
+        assert!(alloc.alloc_offset(2048, 1).is_none());
+        assert_eq!(alloc.alloc_fail_count(), 1);
+    }
+
+    #[test]
+    fn test_reset_to_keeps_prefix() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let table = alloc.alloc(100, 8);
+        let prefix = alloc.used();
+        let scratch = alloc.alloc(256, 64);
+        unsafe { alloc.reset_to(prefix).unwrap() };
+        assert_eq!(alloc.used(), prefix);
+        assert!(alloc.alloc(8, 1) >= unsafe { table.add(100) });
+
+        // Rewinding is repeatable, but never forward.
+        unsafe { alloc.reset_to(prefix).unwrap() };
+        assert_eq!(
+            unsafe { alloc.reset_to(prefix + 1) },
+            Err(BumpError::OffsetAhead)
+        );
+        assert_eq!(
+            unsafe { alloc.reset_to(usize::MAX) },
+            Err(BumpError::OffsetAhead)
+        );
+        assert_eq!(alloc.alloc(256, 64), scratch);
+        unsafe { alloc.reset_to(0).unwrap() };
+        assert_eq!(alloc.used(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "stats-histogram")]
+    fn test_size_histogram() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        for size in [1, 2, 3, 64, 65, 1024] {
+            let _ = alloc.alloc(size, 1);
+        }
+        assert!(alloc.alloc(8192, 1).is_null());
+
+        let histogram = alloc.size_histogram();
+        assert_eq!(histogram[0], 1);
+        assert_eq!(histogram[1], 1);
+        assert_eq!(histogram[2], 1);
+        assert_eq!(histogram[6], 1);
+        assert_eq!(histogram[7], 1);
+        assert_eq!(histogram[10], 1);
+        // Failed allocations are not counted.
+        assert_eq!(histogram.iter().sum::<usize>(), 6);
+        assert_eq!(size_class(usize::MAX), SIZE_CLASSES - 1);
+
+        alloc.reset_stats();
+        assert_eq!(alloc.size_histogram(), [0; SIZE_CLASSES]);
+    }
+
+    #[test]
+    fn test_shrink_in_place() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let first = alloc.alloc(256, 1);
+        let last = alloc.alloc(256, 1);
+        unsafe {
+            // Only the most recent block can shrink.
+            assert!(!alloc.shrink_in_place(first, 256, 64));
+            assert!(alloc.shrink_in_place(last, 256, 64));
+        }
+        assert_eq!(alloc.used(), 256 + 64);
+        assert_eq!(alloc.alloc(8, 1), last.wrapping_add(64));
+    }
+
+    #[test]
+    fn test_reset_stats_keeps_memory() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let ptr = alloc.alloc(512, 1);
+        unsafe { ptr.write(0x42) };
+        assert!(alloc.alloc(1024, 1).is_null());
+        let _ = alloc.alloc(128, 1);
+
+        alloc.reset_stats();
+        assert_eq!(alloc.alloc_count(), 0);
+        assert_eq!(alloc.alloc_fail_count(), 0);
+        assert_eq!(alloc.peak_used(), 640);
+        assert_eq!(alloc.used(), 640);
+        assert_eq!(unsafe { *ptr }, 0x42);
+
+        let _ = alloc.alloc(8, 1);
+        assert_eq!(alloc.alloc_count(), 1);
+        assert_eq!(alloc.peak_used(), 648);
+    }
+
+    #[cfg(feature = "growable")]
+    #[test]
+    fn test_growable_reset_stats_keeps_chunk_usage_in_peak() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.set_growable(true);
+
+        let _ = alloc.alloc(512, 1);
+        assert!(!alloc.alloc(1024, 1).is_null());
+        alloc.reset_stats();
+        assert!(alloc.used() > buffer.len());
+        assert_eq!(alloc.peak_used(), alloc.used());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_prezero_skips_zeroing() {
+        let mut buffer = vec![0xAAu8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        unsafe { alloc.prezero(512) };
+        let ptr = alloc.alloc_zeroed(256, 1);
+        unsafe {
+            assert!(core::slice::from_raw_parts(ptr, 512)
+                .iter()
+                .all(|&b| b == 0));
+            // Scribble on prezeroed memory the arena has not handed out; a
+            // skipped zeroing shows through.
+            ptr.add(256).write(0x11);
+        }
+        let next = alloc.alloc_zeroed(256, 1);
+        assert_eq!(unsafe { *next }, 0x11);
+
+        // Past the prezeroed prefix, zeroing happens as usual.
+        let tail = alloc.alloc_zeroed(256, 1);
+        assert!(unsafe { core::slice::from_raw_parts(tail, 256) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_alignment_waste() {
+        let mut buffer = vec![0u64; 512];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr() as *mut u8, 4096) };
+        let base = alloc.base_ptr() as usize;
+
+        let _ = alloc.alloc(8, 8);
+        let first = alloc.last_padding();
+        assert_eq!(first, base.next_multiple_of(8) - base);
+
+        let _ = alloc.alloc(8, 256);
+        let padding = (base + first + 8).next_multiple_of(256) - (base + first + 8);
+        assert_eq!(alloc.last_padding(), padding);
+
+        let _ = alloc.alloc(16, 8);
+        assert_eq!(alloc.last_padding(), 0);
+        assert_eq!(alloc.alignment_waste(), first + padding);
+
+        unsafe { alloc.reset() };
+        assert_eq!(alloc.alignment_waste(), first + padding);
+    }
+
+    #[test]
+    fn test_base_alignment() {
+        let mut buffer = vec![0u64; 512];
+        let base = buffer.as_mut_ptr() as *mut u8;
+
+        let alloc = unsafe { BumpAlloc::new(base, 4096) };
+        assert!(alloc.base_alignment() >= 8);
+        assert_eq!(base as usize % alloc.base_alignment(), 0);
+        assert_ne!(base as usize % (alloc.base_alignment() * 2), 0);
+
+        // A multiple of 16 plus 8 is aligned to exactly 8.
+        let skew = (base as usize).next_multiple_of(16) + 8 - base as usize;
+        let offset = unsafe { BumpAlloc::new(base.add(skew), 1024) };
+        assert_eq!(offset.base_alignment(), 8);
+    }
+
+    #[test]
+    fn test_alloc_layout() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(1, 1);
+        let ptr = alloc.alloc_layout(Layout::new::<[u64; 4]>());
         assert!(!ptr.is_null());
+        assert_eq!(ptr as usize % core::mem::align_of::<u64>(), 0);
+        assert_eq!(alloc.used(), 8 + 32);
+    }
+
+    #[test]
+    fn test_alloc_n() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(1, 1);
+        let base = alloc.alloc_n(10, 24, 16);
+        assert!(!base.is_null());
+        assert_eq!(base as usize % 16, 0);
+        assert_eq!(alloc.alloc_count(), 2);
+        // Ten 32-byte slots.
+        assert_eq!(
+            alloc.used(),
+            base as usize - alloc.base_ptr() as usize + 10 * 32
+        );
+
+        assert!(alloc.alloc_n(1000, 8, 8).is_null());
+        assert!(alloc.alloc_n(usize::MAX, 8, 8).is_null());
+        assert!(alloc.alloc_n(2, usize::MAX, 8).is_null());
+    }
+
+    #[test]
+    fn test_can_alloc_counts_alignment_padding() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let base = buffer.as_ptr() as usize;
+
+        let _ = alloc.alloc(1, 1);
+        let padding = (base + 1).next_multiple_of(256) - (base + 1);
+        let fits = alloc.remaining() - padding;
+        assert!(alloc.can_alloc(fits, 256));
+        assert!(!alloc.can_alloc(fits + 1, 256));
+        assert!(alloc.can_alloc(alloc.remaining(), 1));
+        assert!(!alloc.can_alloc(usize::MAX, 1));
+
+        // Querying does not move the cursor.
+        assert_eq!(alloc.used(), 1);
+        assert!(!alloc.alloc(fits, 256).is_null());
+    }
+
+    #[test]
+    fn test_restore_rejects_marker_from_before_reset() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(64, 8);
+        let marker = alloc.checkpoint();
+        unsafe { alloc.reset() };
+        let _ = alloc.alloc(512, 8);
+
+        assert_eq!(alloc.check_marker(marker), Err(BumpError::StaleMarker));
+        assert_eq!(
+            unsafe { alloc.restore(marker) },
+            Err(BumpError::StaleMarker)
+        );
+        assert_eq!(alloc.used(), 512);
+    }
+
+    #[cfg(feature = "serialized")]
+    #[test]
+    fn test_serialized_offsets_are_reproducible() {
+        use std::sync::Arc;
+        use std::thread;
+
+        const THREADS: usize = 4;
+        const ROUNDS: usize = 16;
+
+        fn run() -> Vec<Vec<usize>> {
+            let mut buffer = vec![0u8; 64 * 1024];
+            let alloc =
+                Arc::new(unsafe { BumpAlloc::new_serialized(buffer.as_mut_ptr(), buffer.len()) });
+            let turn = Arc::new(AtomicUsize::new(0));
+
+            let handles: Vec<_> = (0..THREADS)
+                .map(|t| {
+                    let alloc = Arc::clone(&alloc);
+                    let turn = Arc::clone(&turn);
+                    thread::spawn(move || {
+                        // Fixed work distribution: request i belongs to thread i % THREADS
+                        let mut offsets = Vec::new();
+                        for i in (t..THREADS * ROUNDS).step_by(THREADS) {
+                            while turn.load(Ordering::Acquire) != i {
+                                thread::yield_now();
+                            }
+                            let ptr = alloc.alloc(8 * (t + 1) + i, 8);
+                            offsets.push(ptr as usize - alloc.base_ptr() as usize);
+                            turn.store(i + 1, Ordering::Release);
+                        }
+                        offsets
+                    })
+                })
+                .collect();
+
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        }
+
+        let first = run();
+        for _ in 0..3 {
+            assert_eq!(run(), first);
+        }
+    }
+
+    #[test]
+    fn test_cas_retries_under_contention() {
+        use std::thread;
+
+        let mut buffer = vec![0u8; 1024 * 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        assert_eq!(alloc.cas_retries(), 0);
+
+        // Retries need threads actually running at the same time.
+        let parallel = thread::available_parallelism().is_ok_and(|n| n.get() > 1);
+        for _ in 0..16 {
+            thread::scope(|s| {
+                for _ in 0..4 {
+                    s.spawn(|| {
+                        for _ in 0..4096 {
+                            assert!(!alloc.alloc(8, 8).is_null());
+                        }
+                    });
+                }
+            });
+            if alloc.cas_retries() > 0 || !parallel {
+                break;
+            }
+            unsafe { alloc.reset() };
+        }
+        if parallel {
+            assert!(alloc.cas_retries() > 0);
+        }
+
+        alloc.reset_stats();
+        assert_eq!(alloc.cas_retries(), 0);
+    }
+
+    #[test]
+    fn test_iter_allocations() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let _ = alloc.alloc(8, 8);
+        assert_eq!(alloc.iter_allocations().count(), 0);
+
+        alloc.enable_recording();
+        let a = alloc.alloc(100, 8);
+        let b = alloc.alloc(32, 32);
+        let c = alloc.alloc(16, 8);
+        let offset = |p: *mut u8| p as usize - alloc.base_ptr() as usize;
+        let records: Vec<_> = alloc.iter_allocations().collect();
+        assert_eq!(
+            records,
+            [
+                AllocRecord {
+                    offset: offset(a),
+                    size: 100,
+                    align: 8,
+                    phase: 0
+                },
+                AllocRecord {
+                    offset: offset(b),
+                    size: 32,
+                    align: 32,
+                    phase: 0
+                },
+                AllocRecord {
+                    offset: offset(c),
+                    size: 16,
+                    align: 8,
+                    phase: 0
+                },
+            ]
+        );
+
+        unsafe { assert!(alloc.dealloc_last(c, 16)) };
+        assert_eq!(alloc.iter_allocations().map(|r| r.size).max(), Some(100));
+        assert_eq!(alloc.iter_allocations().count(), 2);
+        unsafe { alloc.reset() };
+        assert_eq!(alloc.iter_allocations().count(), 0);
+    }
+
+    #[cfg(feature = "sanitizer")]
+    #[test]
+    fn test_sanitizer_poisons_free_memory() {
+        extern "C" {
+            fn __asan_address_is_poisoned(addr: *const core::ffi::c_void) -> i32;
+        }
+        let poisoned = |addr: usize| unsafe { __asan_address_is_poisoned(addr as *const _) != 0 };
+
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let base = alloc.base_ptr() as usize;
+        assert!(poisoned(base));
+
+        let a = alloc.alloc(16, 8) as usize;
+        let b = alloc.alloc(16, 64) as usize;
+        assert!(!poisoned(a) && !poisoned(a + 15));
+        // Padding and free memory past a block are off-limits.
+        assert!(poisoned(a + 16));
+        assert!(poisoned(b + 16));
+
+        unsafe { assert!(alloc.dealloc_last(b as *mut u8, 16)) };
+        assert!(poisoned(b));
+        unsafe { alloc.reset() };
+        assert!(poisoned(a));
+
+        drop(alloc);
+        assert!(!poisoned(base));
+    }
+
+    #[test]
+    fn test_take_exclusive() {
+        let mut buffer = vec![0xAAu8; 4096];
+        let mut alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let first = alloc.alloc(8, 8);
+
+        let (a, b) = {
+            let mut exclusive = alloc.take_exclusive();
+            let a = exclusive.alloc(3, 1);
+            assert_eq!(a as usize, first as usize + 8);
+            let b = exclusive.alloc_zeroed(64, 64);
+            assert_eq!(b as usize % 64, 0);
+            assert!(unsafe { core::slice::from_raw_parts(b, 64) }
+                .iter()
+                .all(|&x| x == 0));
+            assert_eq!(exclusive.used(), b as usize + 64 - first as usize);
+            assert_eq!(exclusive.remaining(), 4096 - exclusive.used());
+            assert!(exclusive.alloc(8192, 8).is_null());
+            (a, b)
+        };
+        assert!(a < b);
+
+        // The shared path picks up where the handle left off.
+        assert_eq!(alloc.used(), b as usize + 64 - first as usize);
+        assert_eq!(alloc.alloc_count(), 3);
+        assert_eq!(alloc.alloc_fail_count(), 1);
+        assert_eq!(alloc.alignment_waste(), b as usize - (a as usize + 3));
+        assert_eq!(alloc.alloc(8, 8) as usize, b as usize + 64);
+
+        alloc.enable_recording();
+        let c = alloc.take_exclusive().alloc(16, 8);
+        assert_eq!(alloc.iter_allocations().last().map(|r| r.size), Some(16));
+        assert!(alloc.contains(c));
+    }
+
+    #[test]
+    fn test_grow_down() {
+        let mut buffer = vec![0u8; 4096];
+        let (base, len) = (buffer.as_mut_ptr(), buffer.len());
+        let low = unsafe { BumpAlloc::new(base, len) };
+        let high = unsafe { BumpAlloc::new(base, len) }.with_direction(GrowDirection::Down);
+        assert_eq!(high.direction(), GrowDirection::Down);
+        let limit = base as usize + len;
+
+        let a = high.alloc(100, 1);
+        assert_eq!(a as usize, limit - 100);
+        let b = high.alloc(24, 16);
+        assert_eq!(b as usize % 16, 0);
+        assert!(b as usize + 24 <= a as usize);
+        let c = high.alloc_with_phase(8, 64, 8);
+        assert_eq!(c as usize % 64, 8);
+        assert!(c < b);
+        assert_eq!(high.used(), limit - c as usize);
+        assert_eq!(high.remaining(), len - high.used());
+
+        // Growing toward each other, the arenas collide once their usage
+        // adds up to the buffer.
+        let d = low.alloc(1024, 8);
+        assert!((d as usize + 1024) <= c as usize);
+        assert!(low.used() + high.used() <= len);
+        let _ = low.alloc(len - high.used() - low.used() + 1, 1);
+        assert!(low.used() + high.used() > len);
+
+        assert!(!high.grow_in_place(c, 8, 16));
         unsafe {
-            std::ptr::write_bytes(ptr, 0xAB, 512);
+            assert!(!high.shrink_in_place(c, 8, 4));
+            assert!(high.dealloc_last(c, 8));
+            assert!(!high.dealloc_last(a, 100));
+            assert_eq!(high.reset_keeping_last(1).err(), Some(BumpError::GrowsDown));
         }
 
-        // Secure reset
-        unsafe { alloc.secure_reset() };
+        high.enable_recording();
+        let kept = high.alloc(16, 8);
+        let marker = high.checkpoint();
+        let e = high.alloc(16, 8);
+        assert_eq!(high.iter_allocations().count(), 2);
+        unsafe { high.restore(marker).unwrap() };
+        let records: Vec<_> = high.iter_allocations().collect();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].offset, kept as usize - base as usize);
+        assert_eq!(high.alloc(16, 8), e);
 
-        // Verify memory is zeroed
-        for i in 0..1024 {
-            assert_eq!(buffer[i], 0, "Byte {} not zeroed", i);
+        unsafe { high.reset() };
+        assert_eq!(high.used(), 0);
+        assert_eq!(high.alloc(100, 1), a);
+        let z = high.alloc_zeroed(64, 8);
+        assert!(unsafe { core::slice::from_raw_parts(z, 64) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "std"))]
+    fn test_restore_checks_taints_in_cursor_space() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) }
+            .with_direction(GrowDirection::Down);
+
+        // A secret below the marker survives the rewind...
+        let secret = alloc.alloc(64, 8);
+        alloc.taint(secret, 64, 8);
+        let marker = alloc.checkpoint();
+        let _ = alloc.alloc(64, 8);
+        unsafe { alloc.restore(marker).unwrap() };
+
+        // ...one past it is discarded.
+        let secret = alloc.alloc(64, 8);
+        alloc.taint(secret, 64, 8);
+        let leak = std::panic::catch_unwind(|| unsafe { alloc.restore(marker) });
+        let message = *leak.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("restore discards 64 secret bytes"));
+        alloc.tainted.lock().clear();
+    }
+
+    #[test]
+    fn test_reset_keeping_last_requires_recording() {
+        let mut buffer = vec![0u8; 1024];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+
+        let _ = alloc.alloc(64, 8);
+        let result = unsafe { alloc.reset_keeping_last(1) };
+        assert_eq!(result, Err(BumpError::RecordingDisabled));
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.enable_recording();
+        for (size, align) in [(3, 1), (100, 64), (8, 8), (1000, 16)] {
+            assert!(!alloc.alloc(size, align).is_null());
         }
+        assert_eq!(alloc.check_invariants(), Ok(()));
+
+        // Corrupt the records the way an overflowing placement would.
+        let first = alloc.lock_records()[0];
+        alloc.lock_records().push(AllocRecord { size: 16, ..first });
+        assert!(matches!(
+            alloc.check_invariants(),
+            Err(InvariantError::Overlap(..))
+        ));
+        alloc.lock_records().push(AllocRecord {
+            offset: 4000,
+            size: 8,
+            align: 8,
+            phase: 0,
+        });
+        assert!(matches!(
+            alloc.check_invariants(),
+            Err(InvariantError::PastCursor(_))
+        ));
+
+        unsafe { alloc.reset() };
+        assert_eq!(alloc.check_invariants(), Ok(()));
+        alloc.peak.store(0, Ordering::Relaxed);
+        let _ = alloc.alloc(64, 8);
+        alloc.peak.store(0, Ordering::Relaxed);
+        assert_eq!(
+            alloc.check_invariants(),
+            Err(InvariantError::PeakBelowUsed { peak: 0, used: 64 })
+        );
+    }
+
+    #[test]
+    fn test_check_invariants_with_phase() {
+        let mut buffer = vec![0u8; 4 * CACHE_COLOR_SPAN];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.enable_recording();
+        alloc.set_color_stride(4096);
+
+        assert!(!alloc.alloc_colored(100).is_null());
+        assert!(!alloc.alloc_colored(100).is_null());
+        assert!(!alloc.alloc_with_phase(10, 64, 24).is_null());
+        assert!(alloc.iter_allocations().any(|r| r.phase == 4096));
+        assert_eq!(alloc.check_invariants(), Ok(()));
+
+        let mut shifted = alloc.lock_records()[2];
+        shifted.phase = 8;
+        alloc.lock_records()[2] = shifted;
+        assert_eq!(
+            alloc.check_invariants(),
+            Err(InvariantError::Misaligned(shifted))
+        );
+    }
+
+    #[test]
+    fn test_reset_keeping_last_carries_buffer() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.enable_recording();
+
+        let _ = alloc.alloc(1000, 8);
+        let result = alloc.alloc(256, 8);
+        unsafe {
+            for i in 0..256 {
+                result.add(i).write(i as u8);
+            }
+        }
+
+        let relocations = unsafe { alloc.reset_keeping_last(1).unwrap() };
+        assert_eq!(relocations.len(), 1);
+        assert_eq!(relocations[0].old, result);
+        assert_eq!(relocations[0].new, alloc.base_ptr());
+        assert_eq!(alloc.used(), 256);
+
+        // The carried buffer survived the move
+        let moved = relocations[0].new;
+        unsafe {
+            for i in 0..256 {
+                assert_eq!(moved.add(i).read(), i as u8);
+            }
+        }
+
+        // New allocations land after the carried buffer
+        let next = alloc.alloc(8, 8);
+        assert_eq!(next as usize, moved as usize + 256);
+    }
+
+    #[test]
+    fn test_reset_keeping_last_keeps_phase() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        alloc.enable_recording();
+
+        let _ = alloc.alloc(1000, 8);
+        let kept = alloc.alloc_with_phase(100, 64, 24);
+        assert_eq!(kept as usize % 64, 24);
+        #[cfg(feature = "growable")]
+        {
+            alloc.set_growable(true);
+            assert!(!alloc.alloc(8192, 8).is_null());
+        }
+
+        let relocations = unsafe { alloc.reset_keeping_last(1).unwrap() };
+        let moved = relocations[0].new as usize;
+        assert_eq!(moved % 64, 24);
+        assert!(moved - (alloc.base_ptr() as usize) < 64);
+        assert_eq!(alloc.generation(), 1);
+        assert_eq!(alloc.capacity(), 4096);
     }
 }
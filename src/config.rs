@@ -9,16 +9,46 @@
 
 /// Size of the Witness Arena in bytes.
 /// Used for private ZK inputs requiring secure wiping.
+#[cfg(not(target_pointer_width = "32"))]
 pub const WITNESS_ARENA_SIZE: usize = 128 * 1024 * 1024; // 128 MB
 
 /// Size of the Polynomial Arena in bytes.
 /// Used for FFT/NTT coefficient vectors - needs to be large for complex circuits.
+#[cfg(not(target_pointer_width = "32"))]
 pub const POLY_ARENA_SIZE: usize = 1024 * 1024 * 1024; // 1 GB
 
 /// Size of the Scratch Arena in bytes.
 /// Used for temporary computation buffers.
+#[cfg(not(target_pointer_width = "32"))]
 pub const SCRATCH_ARENA_SIZE: usize = 256 * 1024 * 1024; // 256 MB
 
+// A 32-bit address space (often much less on wasm32 and embedded targets)
+// cannot hold the 64-bit defaults next to the rest of the process.
+
+/// Size of the Witness Arena in bytes.
+/// Used for private ZK inputs requiring secure wiping.
+#[cfg(target_pointer_width = "32")]
+pub const WITNESS_ARENA_SIZE: usize = 16 * 1024 * 1024; // 16 MB
+
+/// Size of the Polynomial Arena in bytes.
+/// Used for FFT/NTT coefficient vectors - needs to be large for complex circuits.
+#[cfg(target_pointer_width = "32")]
+pub const POLY_ARENA_SIZE: usize = 64 * 1024 * 1024; // 64 MB
+
+/// Size of the Scratch Arena in bytes.
+/// Used for temporary computation buffers.
+#[cfg(target_pointer_width = "32")]
+pub const SCRATCH_ARENA_SIZE: usize = 32 * 1024 * 1024; // 32 MB
+
+/// Environment variable overriding [`WITNESS_ARENA_SIZE`] (e.g. `512M`, `2G`).
+pub const WITNESS_SIZE_ENV: &core::ffi::CStr = c"NALLOC_WITNESS_SIZE";
+
+/// Environment variable overriding [`POLY_ARENA_SIZE`].
+pub const POLY_SIZE_ENV: &core::ffi::CStr = c"NALLOC_POLY_SIZE";
+
+/// Environment variable overriding [`SCRATCH_ARENA_SIZE`].
+pub const SCRATCH_SIZE_ENV: &core::ffi::CStr = c"NALLOC_SCRATCH_SIZE";
+
 // ============================================================================
 // Allocation Thresholds
 // ============================================================================
@@ -27,6 +57,19 @@ pub const SCRATCH_ARENA_SIZE: usize = 256 * 1024 * 1024; // 256 MB
 /// Smaller allocations go to the Scratch Arena via GlobalAlloc.
 pub const LARGE_ALLOC_THRESHOLD: usize = 1024 * 1024; // 1 MB
 
+/// Number of largest live allocations captured when an arena is exhausted
+/// in record mode.
+pub const EXHAUSTION_CONTEXT_TOP: usize = 4;
+
+/// Number of power-of-two size classes in an allocation size histogram.
+/// Class `i` counts sizes in `(2^(i-1), 2^i]`; the last class also takes
+/// everything larger.
+pub const SIZE_CLASSES: usize = 48;
+
+/// Number of busy-wait spins a thread waiting for another to initialize
+/// the arenas makes before it starts yielding its time slice.
+pub const INIT_SPIN_LIMIT: u32 = 128;
+
 // ============================================================================
 // Alignment Constants
 // ============================================================================
@@ -35,13 +78,42 @@ pub const LARGE_ALLOC_THRESHOLD: usize = 1024 * 1024; // 1 MB
 /// 64 bytes is optimal for AVX-512 and most modern CPUs.
 pub const CACHE_LINE_ALIGN: usize = 64;
 
-/// Page alignment for huge vector allocations.
-/// 4KB works across Linux, macOS, and Windows.
+/// Minimum page size across supported platforms.
+/// The real page size can be larger (16KB on Apple silicon); query it with
+/// [`sys::page_size`](crate::platform::sys::page_size).
 pub const PAGE_ALIGN: usize = 4096;
 
+/// Granularity at which Windows arenas commit their reservation as the
+/// cursor advances. Bounds the commit charge to what is in use plus 1MB.
+pub const COMMIT_CHUNK: usize = 1024 * 1024;
+
+/// 2MB huge page size (x86-64 and AArch64 with 4KB base pages).
+pub const HUGE_PAGE_2MB: usize = 2 * 1024 * 1024;
+
+/// 1GB huge page size (x86-64).
+pub const HUGE_PAGE_1GB: usize = 1024 * 1024 * 1024;
+
+/// Address requested for the witness arena when
+/// [`ArenaConfig::stable_addresses`](crate::ArenaConfig::stable_addresses)
+/// is set. Unused on targets whose address space cannot hold it.
+pub const ARENA_ADDRESS_HINT_BASE: u64 = 0x2000_0000_0000; // 32 TB
+
+/// Distance between the addresses requested for successive arenas
+/// (witness, polynomial, scratch).
+pub const ARENA_ADDRESS_HINT_STRIDE: u64 = 1 << 40; // 1 TB
+
 /// Default minimum alignment for all allocations.
 pub const DEFAULT_ALIGN: usize = 8;
 
+/// Address span covered by one full pass over the cache sets (the "way size").
+/// Addresses that differ by a multiple of this map to the same cache set.
+/// 64KB matches a typical 1MB, 16-way L2 cache.
+pub const CACHE_COLOR_SPAN: usize = 64 * 1024; // 64 KB
+
+/// Default distance between cache colors for `BumpAlloc::alloc_colored`,
+/// giving 16 colors per [`CACHE_COLOR_SPAN`].
+pub const CACHE_COLOR_STRIDE: usize = 4096;
+
 // ============================================================================
 // Security Constants
 // ============================================================================
@@ -51,5 +123,13 @@ pub const DEFAULT_ALIGN: usize = 8;
 #[cfg(debug_assertions)]
 pub const POISON_PATTERN: u8 = 0xDE;
 
+/// Word written over reset memory with the `poison` feature.
+/// Stale data reads back as repeated `0xDEADBEEF` in native byte order.
+pub const POISON_WORD: u32 = 0xDEAD_BEEF;
+
+/// Default minimum alignment of witness allocations. Keeping every secret
+/// at a cache-line boundary stops small ones from straddling two lines.
+pub const WITNESS_MIN_ALIGN: usize = CACHE_LINE_ALIGN;
+
 /// Secure wipe pattern (zero is standard for cryptographic applications).
 pub const SECURE_WIPE_PATTERN: u8 = 0x00;
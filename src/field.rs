@@ -0,0 +1,121 @@
+//! Field Arena for nalloc.
+//!
+//! The `FieldArena` hands out vectors of fixed-width field elements, such
+//! as Montgomery-form scalars for MSM and NTT:
+//!
+//! - **Packed elements**: No padding between limbs.
+//! - **64-byte aligned base**: The whole vector is ready for AVX-512 loads.
+//! - **Shared storage**: Backed by the polynomial arena.
+
+use crate::bump::BumpAlloc;
+use crate::config::CACHE_LINE_ALIGN;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// Specialized handle for field-element vectors.
+///
+/// Shares its memory with [`PolynomialArena`](crate::PolynomialArena):
+/// resetting either resets both.
+#[derive(Clone)]
+pub struct FieldArena {
+    inner: Arc<BumpAlloc>,
+}
+
+impl FieldArena {
+    /// Create a new `FieldArena` wrapping a `BumpAlloc`.
+    #[inline]
+    pub fn new(inner: Arc<BumpAlloc>) -> Self {
+        Self { inner }
+    }
+
+    /// Allocate `count` packed field elements of `limb_bytes` bytes each.
+    ///
+    /// For 256-bit elements in Montgomery form, `limb_bytes` is 32. The base
+    /// of the vector is 64-byte aligned; elements follow each other with no
+    /// padding. Returns a null pointer if the arena is exhausted or the
+    /// size overflows.
+    #[inline]
+    pub fn alloc_field_vec(&self, count: usize, limb_bytes: usize) -> *mut u8 {
+        debug_assert!(count > 0);
+        debug_assert!(
+            limb_bytes > 0 && limb_bytes.is_multiple_of(8),
+            "limb_bytes must be a non-zero multiple of 8"
+        );
+        match count.checked_mul(limb_bytes) {
+            Some(size) => self.inner.alloc(size, CACHE_LINE_ALIGN),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    /// Like [`alloc_field_vec`](Self::alloc_field_vec), but returns `None`
+    /// instead of a null pointer on failure.
+    #[inline]
+    pub fn alloc_field_vec_nn(&self, count: usize, limb_bytes: usize) -> Option<NonNull<u8>> {
+        NonNull::new(self.alloc_field_vec(count, limb_bytes))
+    }
+
+    /// Reset the underlying polynomial arena.
+    ///
+    /// # Safety
+    /// All previously allocated field and polynomial memory becomes invalid.
+    #[inline]
+    pub unsafe fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// Get the remaining capacity in bytes.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Get the number of bytes currently allocated.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.inner.used()
+    }
+
+    /// Get the total capacity in bytes.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Get the `[base, limit)` addresses of the region; see
+    /// [`BumpAlloc::region`].
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        self.inner.region()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::ArenaManager;
+
+    #[test]
+    fn test_field_vec_packed_and_aligned() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let field = FieldArena::new(manager.polynomial());
+
+        // Knock the cursor off a cache-line boundary first.
+        let _ = manager.polynomial().alloc(8, 8);
+
+        let scalars = field.alloc_field_vec(1000, 32);
+        assert!(!scalars.is_null());
+        assert_eq!(scalars as usize % 64, 0);
+        assert_eq!(field.used(), 64 + 1000 * 32);
+
+        assert!(field.alloc_field_vec(usize::MAX, 32).is_null());
+    }
+
+    #[test]
+    fn test_field_shares_polynomial_arena() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let field = FieldArena::new(manager.polynomial());
+
+        let _ = field.alloc_field_vec_nn(16, 48).unwrap();
+        assert_eq!(manager.polynomial().used(), 16 * 48);
+    }
+}
@@ -40,23 +40,113 @@
 //! unsafe { witness.secure_wipe(); }
 //! ```
 
+//!
+//! # `no_std`
+//!
+//! Disabling the default `std` feature leaves only [`BumpAlloc`] (plus the
+//! configuration constants and [`Zeroable`]), built on `core` and `alloc`.
+//! It can then be constructed over a caller-provided buffer, e.g. a
+//! `static mut` array in an enclave or firmware image, with
+//! [`BumpAlloc::from_slice`] or [`BumpAlloc::from_boxed`], or taken from
+//! a [`StaticArena`] whose size is a const parameter. The OS-backed
+//! arenas, [`NAlloc`] and page locking/releasing all require `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly-allocator-api", feature(allocator_api))]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub mod arena;
+pub mod boxed;
+#[cfg(feature = "std")]
+pub mod bucket;
 pub mod bump;
 pub mod config;
+#[cfg(feature = "std")]
+pub mod field;
+#[cfg(all(feature = "std", unix))]
+pub mod mapped;
+#[cfg(feature = "std")]
+pub mod merkle;
+#[cfg(feature = "cuda")]
+pub mod pinned;
+#[cfg(feature = "std")]
 pub mod platform;
+#[cfg(feature = "std")]
 pub mod polynomial;
+#[cfg(feature = "fuzzing")]
+pub mod replay;
+pub mod static_arena;
+mod sync;
+#[cfg(feature = "std")]
+pub mod transcript;
+#[cfg(feature = "std")]
 pub mod witness;
+pub mod zeroable;
 
-pub use arena::{ArenaManager, ArenaStats};
-pub use bump::BumpAlloc;
+#[cfg(feature = "std")]
+pub use arena::{ArenaConfig, ArenaKind, ArenaManager, ArenaStats, PressureCallback, Snapshot};
+pub use boxed::ArenaBox;
+#[cfg(feature = "std")]
+pub use bucket::BucketArena;
+pub use bump::{
+    AllocRecord, BumpAlloc, BumpError, ExclusiveBump, ExhaustionContext, ExhaustionHook,
+    GrowDirection, InvariantError, Marker, Relocation, ScopeGuard,
+};
 pub use config::*;
-pub use platform::sys;
+#[cfg(feature = "std")]
+pub use field::FieldArena;
+#[cfg(all(feature = "std", unix))]
+pub use mapped::MappedArena;
+#[cfg(feature = "std")]
+pub use merkle::MerkleArena;
+#[cfg(feature = "cuda")]
+pub use pinned::PinnedArena;
+#[cfg(feature = "std")]
+pub use platform::{sys, AllocFailed, NumaPolicy, Protection};
+#[cfg(feature = "std")]
 pub use polynomial::PolynomialArena;
-pub use witness::WitnessArena;
+#[cfg(feature = "fuzzing")]
+pub use replay::AllocOp;
+pub use static_arena::StaticArena;
+#[cfg(feature = "std")]
+pub use transcript::TranscriptArena;
+#[cfg(feature = "async-wipe")]
+pub use witness::WipeHandle;
+#[cfg(feature = "std")]
+pub use witness::{WipeError, WipePolicy, WitnessArena};
+pub use zeroable::Zeroable;
 
-use std::alloc::{GlobalAlloc, Layout};
+#[cfg(feature = "std")]
+use std::alloc::{GlobalAlloc, Layout, System};
+#[cfg(feature = "std")]
+use std::cell::Cell;
+#[cfg(feature = "std")]
 use std::ptr::{copy_nonoverlapping, null_mut};
-use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+#[cfg(feature = "std")]
+use std::sync::atomic::{AtomicBool, AtomicPtr, AtomicU64, AtomicUsize, Ordering};
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    /// Arena selected by [`NAlloc::with_arena`] on this thread, if any.
+    ///
+    /// Const-initialized and without a destructor, so reading it from
+    /// `GlobalAlloc::alloc` never allocates.
+    static ROUTE: Cell<Option<ArenaKind>> = const { Cell::new(None) };
+}
+
+/// Restores the previous thread-local routing when dropped.
+#[cfg(feature = "std")]
+struct RouteGuard(Option<ArenaKind>);
+
+#[cfg(feature = "std")]
+impl Drop for RouteGuard {
+    fn drop(&mut self) {
+        let _ = ROUTE.try_with(|route| route.set(self.0));
+    }
+}
 
 /// The global ZK-optimized allocator.
 ///
@@ -65,21 +155,62 @@ use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
 ///
 /// # Memory Strategy
 ///
-/// - **Large allocations (>1MB)**: Routed to Polynomial Arena (FFT vectors)
+/// - **Large allocations (>1MB)**: Routed to Polynomial Arena (FFT vectors).
+///   The threshold is tunable via [`NAlloc::set_large_alloc_threshold`].
 /// - **Small allocations**: Routed to Scratch Arena (temporary buffers)
 /// - **Witness data**: Use `NAlloc::witness()` for security-critical allocations
+/// - **Exhaustion**: If the chosen arena is full, the request falls back to
+///   the system allocator so the process keeps running. Fallbacks are
+///   counted in [`ArenaStats::system_fallbacks`].
 ///
 /// # Thread Safety
 ///
 /// This allocator uses lock-free atomic operations for initialization and
 /// allocation. It's safe to use from multiple threads concurrently.
+///
+/// # Teardown
+///
+/// Once initialized, the arenas are never unmapped, not even when the
+/// `NAlloc` is dropped, so pointers into them stay valid for the rest of
+/// the process. After [`shutdown`](Self::shutdown), allocations that still
+/// arrive (e.g. from threads outliving `main`) are served by the system
+/// allocator.
+///
+/// # Initialization failure
+///
+/// If the arenas cannot be reserved, `GlobalAlloc` requests return null,
+/// which the standard library reports through
+/// [`handle_alloc_error`](std::alloc::handle_alloc_error). The explicit
+/// handles and statistics panic instead; library users who want the error
+/// should construct with [`try_new`](Self::try_new) or call
+/// [`preinit`](Self::preinit) first.
+#[cfg(feature = "std")]
 pub struct NAlloc {
     /// Pointer to the ArenaManager (null until initialized)
     arenas: AtomicPtr<ArenaManager>,
     /// Flag to prevent re-initialization
     initializing: AtomicBool,
+    /// Allocations larger than this go to the Polynomial Arena
+    large_alloc_threshold: AtomicUsize,
+    /// Allocations served by the system allocator after arena exhaustion
+    system_fallbacks: AtomicUsize,
+    /// Arena configuration used at initialization (`None` reads the environment)
+    config: Option<ArenaConfig>,
+    /// Installed `PressureCallback` (null when none)
+    pressure_callback: AtomicPtr<()>,
+    /// Utilization that triggers the pressure callback, as `f64` bits
+    pressure_threshold: AtomicU64,
+    /// Whether the pressure callback already fired, per `ArenaKind`
+    pressure_fired: [AtomicBool; 3],
+    /// Return null on exhaustion instead of falling back to the system allocator
+    deterministic: bool,
+    /// Set by `shutdown`; later allocations bypass the arenas
+    shut_down: AtomicBool,
+    /// Spins while waiting for another thread's initialization before yielding
+    init_spin_limit: u32,
 }
 
+#[cfg(feature = "std")]
 impl NAlloc {
     /// Create a new `NAlloc` instance.
     ///
@@ -88,63 +219,203 @@ impl NAlloc {
         Self {
             arenas: AtomicPtr::new(null_mut()),
             initializing: AtomicBool::new(false),
+            large_alloc_threshold: AtomicUsize::new(LARGE_ALLOC_THRESHOLD),
+            system_fallbacks: AtomicUsize::new(0),
+            config: None,
+            pressure_callback: AtomicPtr::new(null_mut()),
+            pressure_threshold: AtomicU64::new(0),
+            pressure_fired: [const { AtomicBool::new(false) }; 3],
+            deterministic: false,
+            shut_down: AtomicBool::new(false),
+            init_spin_limit: INIT_SPIN_LIMIT,
         }
     }
 
-    /// Initialize the arenas if not already done.
+    /// Create a new `NAlloc` with its arenas already reserved.
     ///
-    /// This uses a spin-lock pattern with atomic bool to avoid
-    /// the thread-local storage issues that OnceLock has.
+    /// The fallible counterpart of [`new`](Self::new) for library use.
+    pub fn try_new() -> Result<Self, AllocFailed> {
+        let alloc = Self::new();
+        alloc.preinit()?;
+        Ok(alloc)
+    }
+
+    /// Create an `NAlloc` whose addresses follow purely from the order of
+    /// requests, for tests and reproducible-proof audits.
+    ///
+    /// Uses the compiled-in arena sizes (the environment is ignored), a
+    /// single scratch arena, and no optional layout features. Exhaustion
+    /// returns null instead of falling back to the system allocator, so
+    /// every allocation lands in a known arena at a predictable offset.
+    ///
+    /// ```rust
+    /// use std::alloc::{GlobalAlloc, Layout};
+    /// use zk_nalloc::NAlloc;
+    ///
+    /// let alloc = NAlloc::deterministic();
+    /// let layout = Layout::from_size_align(1024, 64).unwrap();
+    /// unsafe {
+    ///     let a = alloc.alloc(layout);
+    ///     let b = alloc.alloc(layout);
+    ///     assert_eq!(b as usize - a as usize, 1024);
+    /// }
+    /// ```
+    pub const fn deterministic() -> Self {
+        Self {
+            config: Some(ArenaConfig::compiled()),
+            deterministic: true,
+            ..Self::new()
+        }
+    }
+
+    /// Check whether this allocator was created with
+    /// [`deterministic`](Self::deterministic).
+    #[inline]
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Start configuring an `NAlloc` for library (non-global) use.
+    ///
+    /// [`new`](Self::new) stays `const` for `#[global_allocator]`; the
+    /// builder produces a runtime-configured instance instead.
+    pub fn builder() -> NAllocBuilder {
+        NAllocBuilder::new()
+    }
+
+    /// Reserve the arenas now instead of on the first allocation.
+    ///
+    /// Call this at a controlled time, e.g. at service startup, so the
+    /// first proof does not pay for the reservation. Unlike lazy
+    /// initialization, which panics, a failure is returned and a later call
+    /// (or allocation) may try again. Does nothing if the arenas already
+    /// exist.
+    pub fn preinit(&self) -> Result<(), AllocFailed> {
+        self.try_init_arenas().map(|_| ())
+    }
+
+    /// Initialize the arenas if not already done, panicking on failure.
     #[cold]
     #[inline(never)]
     fn init_arenas(&self) -> *mut ArenaManager {
-        // Fast path: already initialized
-        let ptr = self.arenas.load(Ordering::Acquire);
-        if !ptr.is_null() {
-            return ptr;
+        match self.try_init_arenas() {
+            Ok(ptr) => ptr,
+            Err(_) => panic!("Failed to initialize nalloc arenas"),
         }
+    }
 
-        // Try to acquire initialization lock
-        if self
-            .initializing
-            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
-            .is_ok()
-        {
-            // We won the race - initialize
-            match ArenaManager::new() {
-                Ok(manager) => {
-                    // Use system allocator to avoid recursive allocation
-                    use std::alloc::{GlobalAlloc, Layout, System};
-                    let layout = Layout::new::<ArenaManager>();
-                    let raw = unsafe { System.alloc(layout) as *mut ArenaManager };
-                    if raw.is_null() {
+    /// Initialize the arenas if not already done.
+    ///
+    /// This uses a spin-lock pattern with atomic bool to avoid
+    /// the thread-local storage issues that OnceLock has.
+    fn try_init_arenas(&self) -> Result<*mut ArenaManager, AllocFailed> {
+        loop {
+            // Fast path: already initialized
+            let ptr = self.arenas.load(Ordering::Acquire);
+            if !ptr.is_null() {
+                return Ok(ptr);
+            }
+
+            // Try to acquire initialization lock
+            if self
+                .initializing
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                // We won the race - initialize
+                let manager = match self.config {
+                    Some(config) => ArenaManager::with_config(config),
+                    None => ArenaManager::new(),
+                };
+                let manager = match manager {
+                    Ok(manager) => manager,
+                    Err(err) => {
+                        // Initialization failed - allow retry
                         self.initializing.store(false, Ordering::Release);
-                        panic!("Failed to allocate ArenaManager");
+                        return Err(err);
                     }
-                    unsafe {
-                        std::ptr::write(raw, manager);
-                    }
-                    self.arenas.store(raw, Ordering::Release);
-                    return raw;
-                }
-                Err(_) => {
-                    // Initialization failed - allow retry
+                };
+                // Use system allocator to avoid recursive allocation
+                let layout = Layout::new::<ArenaManager>();
+                let raw = unsafe { System.alloc(layout) as *mut ArenaManager };
+                if raw.is_null() {
                     self.initializing.store(false, Ordering::Release);
-                    panic!("Failed to initialize nalloc arenas");
+                    return Err(AllocFailed::new(layout.size()));
                 }
+                unsafe {
+                    std::ptr::write(raw, manager);
+                }
+                self.arenas.store(raw, Ordering::Release);
+                return Ok(raw);
             }
-        }
 
-        // Another thread is initializing - spin wait
-        loop {
-            std::hint::spin_loop();
-            let ptr = self.arenas.load(Ordering::Acquire);
-            if !ptr.is_null() {
-                return ptr;
+            // Another thread is initializing - spin briefly, then yield so a
+            // descheduled initializer can run; take over if it gave up.
+            let mut spins = 0;
+            while self.initializing.load(Ordering::Acquire)
+                && self.arenas.load(Ordering::Acquire).is_null()
+            {
+                if spins < self.init_spin_limit {
+                    spins += 1;
+                    std::hint::spin_loop();
+                } else {
+                    std::thread::yield_now();
+                }
             }
         }
     }
 
+    /// The arenas, or `None` if nothing initialized them yet.
+    #[inline(always)]
+    fn initialized_arenas(&self) -> Option<&ArenaManager> {
+        let ptr = self.arenas.load(Ordering::Acquire);
+        // The manager is never freed once published.
+        unsafe { ptr.as_ref() }
+    }
+
+    /// Check whether the arenas have been reserved, without reserving them.
+    ///
+    /// Unlike [`stats`](Self::stats) and the arena handles, which initialize
+    /// the arenas on first use, this is a single atomic load, so a health
+    /// check can tell an idle allocator from an active one.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.initialized_arenas().is_some()
+    }
+
+    /// Stop serving `GlobalAlloc` requests from the arenas.
+    ///
+    /// Call this at the start of process teardown. Every later allocation
+    /// goes to the system allocator, while arena memory handed out before
+    /// stays mapped and can still be read, written and deallocated. The
+    /// explicit handles ([`witness`](Self::witness) and friends) are not
+    /// affected.
+    pub fn shutdown(&self) {
+        self.shut_down.store(true, Ordering::Release);
+    }
+
+    /// Check whether [`shutdown`](Self::shutdown) was called.
+    #[inline]
+    pub fn is_shut_down(&self) -> bool {
+        self.shut_down.load(Ordering::Acquire)
+    }
+
+    /// The arenas, initializing them if needed, or `None` if that fails.
+    #[inline(always)]
+    fn try_get_arenas(&self) -> Option<&ArenaManager> {
+        match self.initialized_arenas() {
+            Some(arenas) => Some(arenas),
+            None => self.init_arenas_or_null(),
+        }
+    }
+
+    #[cold]
+    #[inline(never)]
+    fn init_arenas_or_null(&self) -> Option<&ArenaManager> {
+        let ptr = self.try_init_arenas().ok()?;
+        Some(unsafe { &*ptr })
+    }
+
     #[inline(always)]
     fn get_arenas(&self) -> &ArenaManager {
         let ptr = self.arenas.load(Ordering::Acquire);
@@ -156,6 +427,104 @@ impl NAlloc {
         }
     }
 
+    /// Get the size above which `GlobalAlloc` routes to the polynomial arena.
+    #[inline]
+    pub fn large_alloc_threshold(&self) -> usize {
+        self.large_alloc_threshold.load(Ordering::Relaxed)
+    }
+
+    /// Set the size above which `GlobalAlloc` routes to the polynomial arena.
+    ///
+    /// Defaults to [`LARGE_ALLOC_THRESHOLD`]. Safe to call while allocations
+    /// are in flight; each allocation sees either the old or the new value.
+    #[inline]
+    pub fn set_large_alloc_threshold(&self, bytes: usize) {
+        self.large_alloc_threshold.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Install (or with `None`, remove) a callback run whenever an arena
+    /// allocation fails.
+    ///
+    /// The callback runs inside the failing allocation, before any system
+    /// fallback, so it must not allocate through this allocator.
+    #[inline]
+    pub fn set_exhaustion_hook(&self, hook: Option<ExhaustionHook>) {
+        self.get_arenas().set_exhaustion_hook(hook);
+    }
+
+    /// Install (or with `None`, remove) a callback run when an allocation
+    /// leaves an arena at or above `threshold` utilization (`0.0..=1.0`).
+    ///
+    /// The callback fires at most once per arena; installing a callback
+    /// re-arms it, and resetting an arena (e.g. with
+    /// [`reset_all`](Self::reset_all)) re-arms it for that arena. Only allocations
+    /// routed through `GlobalAlloc` are checked, so the witness arena never
+    /// triggers it. The callback must not allocate through this allocator.
+    pub fn set_pressure_callback(&self, threshold: f64, callback: Option<PressureCallback>) {
+        self.pressure_threshold
+            .store(threshold.to_bits(), Ordering::Relaxed);
+        self.rearm_pressure_callback();
+        let raw = callback.map_or(null_mut(), |f| f as *mut ());
+        self.pressure_callback.store(raw, Ordering::Release);
+    }
+
+    fn rearm_pressure_callback(&self) {
+        for fired in &self.pressure_fired {
+            fired.store(false, Ordering::Relaxed);
+        }
+    }
+
+    fn rearm_pressure_callback_for(&self, kind: ArenaKind) {
+        self.pressure_fired[kind as usize].store(false, Ordering::Relaxed);
+    }
+
+    /// Run the pressure callback if `arena` just crossed the threshold.
+    #[inline(always)]
+    fn check_pressure(&self, kind: ArenaKind, arena: &BumpAlloc) {
+        let callback = self.pressure_callback.load(Ordering::Acquire);
+        if callback.is_null() {
+            return;
+        }
+        let utilization = arena.utilization();
+        let threshold = f64::from_bits(self.pressure_threshold.load(Ordering::Relaxed));
+        if utilization >= threshold
+            && !self.pressure_fired[kind as usize].swap(true, Ordering::AcqRel)
+        {
+            // Safety: only ever stored from a `PressureCallback`.
+            let callback: PressureCallback = unsafe { core::mem::transmute(callback) };
+            callback(kind, utilization);
+        }
+    }
+
+    /// Run `f` with every `GlobalAlloc` allocation on this thread served by
+    /// the `kind` arena.
+    ///
+    /// The size threshold for the polynomial arena is ignored while `f`
+    /// runs. The previous routing is restored when `f` returns or panics,
+    /// so calls nest. Routing is per thread, not per `NAlloc`: threads
+    /// spawned by `f` keep the default strategy.
+    ///
+    /// Memory routed to the witness arena is only reclaimed by
+    /// [`WitnessArena::secure_wipe`], like that of any other witness handle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use zk_nalloc::{ArenaKind, NAlloc};
+    /// use std::alloc::{GlobalAlloc, Layout};
+    ///
+    /// let alloc = NAlloc::new();
+    /// let ptr = alloc.with_arena(ArenaKind::Witness, || unsafe {
+    ///     alloc.alloc(Layout::from_size_align(32, 8).unwrap())
+    /// });
+    /// assert!(!ptr.is_null());
+    /// assert_eq!(alloc.stats().witness_used, 32);
+    /// ```
+    pub fn with_arena<R>(&self, kind: ArenaKind, f: impl FnOnce() -> R) -> R {
+        let _restore = RouteGuard(ROUTE.with(|route| route.replace(Some(kind))));
+        f()
+    }
+
     /// Access the witness arena directly.
     ///
     /// Use this for allocating sensitive private inputs that need
@@ -200,6 +569,15 @@ impl NAlloc {
         PolynomialArena::new(self.get_arenas().polynomial())
     }
 
+    /// Access the polynomial arena through a field-element view.
+    ///
+    /// Use this for packed Montgomery-form scalar vectors (MSM/NTT inputs).
+    /// Shares memory with [`polynomial`](Self::polynomial).
+    #[inline]
+    pub fn field(&self) -> FieldArena {
+        FieldArena::new(self.get_arenas().polynomial())
+    }
+
     /// Access the scratch arena directly.
     ///
     /// Use this for temporary computation space.
@@ -208,6 +586,57 @@ impl NAlloc {
         self.get_arenas().scratch()
     }
 
+    /// Carve a `capacity`-byte transcript buffer out of the scratch arena.
+    ///
+    /// The buffer is private to the returned handle, so appends stay
+    /// contiguous no matter what else allocates. It lives until the scratch
+    /// arena is reset. Returns `None` if the scratch arena cannot fit it.
+    pub fn transcript(&self, capacity: usize) -> Option<TranscriptArena> {
+        let inner = Self::carve(&self.scratch(), capacity, "transcript")?;
+        Some(TranscriptArena::new(inner))
+    }
+
+    /// Carve a `capacity`-byte MSM bucket region out of the polynomial arena.
+    ///
+    /// The region is private to the returned handle, so
+    /// [`reset_buckets`](BucketArena::reset_buckets) only discards buckets.
+    /// It lives until the polynomial arena is reset. Returns `None` if the
+    /// polynomial arena cannot fit it.
+    pub fn buckets(&self, capacity: usize) -> Option<BucketArena> {
+        let inner = Self::carve(&self.get_arenas().polynomial(), capacity, "buckets")?;
+        Some(BucketArena::new(inner))
+    }
+
+    /// Carve a `capacity`-byte Merkle tree region out of the polynomial
+    /// arena.
+    ///
+    /// The region is private to the returned handle, so levels stay back to
+    /// back and [`reset_tree`](MerkleArena::reset_tree) only discards the
+    /// tree. It lives until the polynomial arena is reset. Returns `None` if
+    /// the polynomial arena cannot fit it.
+    pub fn merkle(&self, capacity: usize) -> Option<MerkleArena> {
+        let inner = Self::carve(&self.get_arenas().polynomial(), capacity, "merkle")?;
+        Some(MerkleArena::new(inner))
+    }
+
+    /// Allocate `capacity` bytes from `arena` and wrap them in a private
+    /// `BumpAlloc`.
+    fn carve(
+        arena: &BumpAlloc,
+        capacity: usize,
+        label: &'static str,
+    ) -> Option<std::sync::Arc<BumpAlloc>> {
+        if capacity == 0 {
+            return None;
+        }
+        let region = arena.alloc(capacity, CACHE_LINE_ALIGN);
+        if region.is_null() {
+            return None;
+        }
+        let inner = unsafe { BumpAlloc::new(region, capacity) }.with_label(label);
+        Some(std::sync::Arc::new(inner))
+    }
+
     /// Reset all arenas, freeing all allocated memory.
     ///
     /// The witness arena is securely wiped before reset.
@@ -216,16 +645,187 @@ impl NAlloc {
     /// This will invalidate all previously allocated memory.
     pub unsafe fn reset_all(&self) {
         self.get_arenas().reset_all();
+        self.rearm_pressure_callback();
+    }
+
+    /// Securely wipe and reset only the witness arena.
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the witness arena.
+    pub unsafe fn reset_witness(&self) {
+        self.get_arenas().reset_witness();
+        self.rearm_pressure_callback_for(ArenaKind::Witness);
+    }
+
+    /// Reset only the polynomial arena, keeping witness and scratch data.
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the polynomial arena.
+    pub unsafe fn reset_polynomial(&self) {
+        self.get_arenas().reset_polynomial();
+        self.rearm_pressure_callback_for(ArenaKind::Polynomial);
+    }
+
+    /// Reset only the scratch arena, e.g. between inner proof rounds.
+    ///
+    /// # Safety
+    /// This will invalidate all memory previously allocated from the scratch arena.
+    pub unsafe fn reset_scratch(&self) {
+        self.get_arenas().reset_scratch();
+        self.rearm_pressure_callback_for(ArenaKind::Scratch);
     }
 
     /// Get statistics about arena usage.
     ///
     /// Useful for monitoring memory consumption and tuning arena sizes.
     pub fn stats(&self) -> ArenaStats {
-        self.get_arenas().stats()
+        ArenaStats {
+            system_fallbacks: self.system_fallbacks.load(Ordering::Relaxed),
+            ..self.get_arenas().stats()
+        }
     }
+
+    /// Restart the statistics without freeing any memory, including the
+    /// count of system fallbacks. See [`ArenaManager::reset_stats`].
+    pub fn reset_stats(&self) {
+        self.get_arenas().reset_stats();
+        self.system_fallbacks.store(0, Ordering::Relaxed);
+    }
+
+    /// Emit the current [`stats`](Self::stats) through the `metrics` facade.
+    ///
+    /// Call this periodically (e.g. from a scrape handler); the installed
+    /// `metrics` recorder decides where the values end up. See
+    /// [`ArenaStats::export_metrics`] for the metric names.
+    #[cfg(feature = "metrics")]
+    pub fn export_metrics(&self) {
+        self.stats().export_metrics();
+    }
+
+    /// Serve an allocation the arenas could not fit from the system allocator.
+    #[cold]
+    #[inline(never)]
+    unsafe fn alloc_fallback(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.system_fallbacks.fetch_add(1, Ordering::Relaxed);
+        }
+        ptr
+    }
+}
+
+/// Builder for a configured [`NAlloc`].
+///
+/// Sizes not set explicitly follow [`ArenaConfig::from_env`]. The arenas
+/// are still created lazily, on first use of the built allocator.
+///
+/// ```rust
+/// use zk_nalloc::NAlloc;
+///
+/// let alloc = NAlloc::builder()
+///     .witness_size(16 * 1024 * 1024)
+///     .poly_size(256 * 1024 * 1024)
+///     .scratch_size(64 * 1024 * 1024)
+///     .large_threshold(256 * 1024)
+///     .build();
+/// assert_eq!(alloc.stats().witness_capacity, 16 * 1024 * 1024);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct NAllocBuilder {
+    config: ArenaConfig,
+    large_threshold: usize,
+    init_spin_limit: u32,
 }
 
+#[cfg(feature = "std")]
+impl NAllocBuilder {
+    /// Create a builder with the default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: ArenaConfig::from_env(),
+            large_threshold: LARGE_ALLOC_THRESHOLD,
+            init_spin_limit: INIT_SPIN_LIMIT,
+        }
+    }
+
+    /// Set the witness arena size in bytes.
+    pub fn witness_size(mut self, bytes: usize) -> Self {
+        self.config.witness_size = bytes;
+        self
+    }
+
+    /// Set the polynomial arena size in bytes.
+    pub fn poly_size(mut self, bytes: usize) -> Self {
+        self.config.poly_size = bytes;
+        self
+    }
+
+    /// Set the scratch arena size in bytes.
+    pub fn scratch_size(mut self, bytes: usize) -> Self {
+        self.config.scratch_size = bytes;
+        self
+    }
+
+    /// Set the size above which `GlobalAlloc` routes to the polynomial arena.
+    pub fn large_threshold(mut self, bytes: usize) -> Self {
+        self.large_threshold = bytes;
+        self
+    }
+
+    /// Lock the witness arena into physical memory when it is created.
+    pub fn mlock_witness(mut self, enabled: bool) -> Self {
+        self.config.lock_witness = enabled;
+        self
+    }
+
+    /// Fault in every arena page at initialization.
+    pub fn prefault(mut self, enabled: bool) -> Self {
+        self.config.prefault = enabled;
+        self
+    }
+
+    /// Set how many times a thread waiting for another to initialize the
+    /// arenas spins before it yields instead. Defaults to
+    /// [`INIT_SPIN_LIMIT`]; `0` yields right away.
+    pub fn init_spin_limit(mut self, spins: u32) -> Self {
+        self.init_spin_limit = spins;
+        self
+    }
+
+    /// Replace the whole arena configuration.
+    pub fn arena_config(mut self, config: ArenaConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Build the configured allocator.
+    pub fn build(self) -> NAlloc {
+        NAlloc {
+            large_alloc_threshold: AtomicUsize::new(self.large_threshold),
+            config: Some(self.config),
+            init_spin_limit: self.init_spin_limit,
+            ..NAlloc::new()
+        }
+    }
+
+    /// Build the configured allocator and reserve its arenas immediately,
+    /// returning the error if that fails.
+    pub fn try_build(self) -> Result<NAlloc, AllocFailed> {
+        let alloc = self.build();
+        alloc.preinit()?;
+        Ok(alloc)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for NAllocBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
 impl Default for NAlloc {
     fn default() -> Self {
         Self::new()
@@ -233,52 +833,127 @@ impl Default for NAlloc {
 }
 
 // Safety: NAlloc uses atomic operations for all shared state
+#[cfg(feature = "std")]
 unsafe impl Send for NAlloc {}
+#[cfg(feature = "std")]
 unsafe impl Sync for NAlloc {}
 
+#[cfg(feature = "std")]
 unsafe impl GlobalAlloc for NAlloc {
     #[inline(always)]
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
-        debug_assert!(layout.size() > 0);
         debug_assert!(layout.align() > 0);
         debug_assert!(layout.align().is_power_of_two());
 
-        let arenas = self.get_arenas();
+        // A zero-sized block needs no memory: hand out an aligned dangling
+        // pointer rather than the cursor, which the next allocation owns.
+        if layout.size() == 0 {
+            return layout.align() as *mut u8;
+        }
+        if self.is_shut_down() {
+            return System.alloc(layout);
+        }
+        // Panicking here would allocate; report the failure as OOM instead.
+        let Some(arenas) = self.try_get_arenas() else {
+            return null_mut();
+        };
 
         // Strategy:
         // 1. Large allocations (> threshold) go to Polynomial Arena (likely vectors)
         // 2. Smaller allocations go to Scratch Arena
         // 3. User can explicitly use Witness Arena via NAlloc::witness()
+        // 4. Inside `with_arena`, everything goes to the chosen arena
 
-        if layout.size() > LARGE_ALLOC_THRESHOLD {
-            arenas.polynomial().alloc(layout.size(), layout.align())
-        } else {
-            arenas.scratch().alloc(layout.size(), layout.align())
+        let (kind, arena) = match ROUTE.try_with(Cell::get).ok().flatten() {
+            Some(ArenaKind::Witness) => (ArenaKind::Witness, arenas.witness()),
+            Some(ArenaKind::Polynomial) => (ArenaKind::Polynomial, arenas.polynomial()),
+            Some(ArenaKind::Scratch) => (ArenaKind::Scratch, arenas.scratch()),
+            None if layout.size() > self.large_alloc_threshold() => {
+                (ArenaKind::Polynomial, arenas.polynomial())
+            }
+            None => (ArenaKind::Scratch, arenas.scratch()),
+        };
+
+        let ptr = arena.alloc(layout.size(), layout.align());
+        if ptr.is_null() {
+            if self.deterministic {
+                return ptr;
+            }
+            return self.alloc_fallback(layout);
         }
+        self.check_pressure(kind, &arena);
+        ptr
     }
 
     #[inline(always)]
-    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
         // Individual deallocation is a no-op in a bump allocator.
-        // Memory is reclaimed by calling reset() on the arena.
+        // Memory is reclaimed by calling reset() on the arena, except that
+        // the most recent allocation rewinds the cursor (LIFO reuse).
+        // Only pointers from the system fallback are actually freed.
+        // Uninitialized arenas mean the pointer came from the system.
+        if layout.size() == 0 {
+            return;
+        }
+        let Some(arenas) = self.initialized_arenas() else {
+            return System.dealloc(ptr, layout);
+        };
+        if self.is_shut_down() {
+            if !arenas.contains(ptr) {
+                System.dealloc(ptr, layout);
+            }
+            return;
+        }
+        if arenas.scratch().dealloc_last(ptr, layout.size())
+            || arenas.polynomial().dealloc_last(ptr, layout.size())
+        {
+            return;
+        }
+        if !arenas.contains(ptr) {
+            System.dealloc(ptr, layout);
+        }
     }
 
     #[inline(always)]
     unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
         debug_assert!(!ptr.is_null());
-        debug_assert!(layout.size() > 0);
-        debug_assert!(new_size > 0);
 
         let old_size = layout.size();
+        // A size that overflows `isize` once aligned is not a valid layout.
+        let Ok(new_layout) = Layout::from_size_align(new_size, layout.align()) else {
+            return null_mut();
+        };
+        // Zero-sized blocks are dangling pointers, never arena memory.
+        if old_size == 0 || new_size == 0 {
+            self.dealloc(ptr, layout);
+            return self.alloc(new_layout);
+        }
+
+        // System fallback memory must be resized by the system allocator.
+        let Some(arenas) = self.initialized_arenas() else {
+            return System.realloc(ptr, layout, new_size);
+        };
+        let Some(owner) = arenas.owner_of(ptr) else {
+            return System.realloc(ptr, layout, new_size);
+        };
 
-        // If the new size is smaller or equal, just return the same pointer.
-        // (The bump allocator doesn't shrink.)
+        // A smaller block stays in place. If it was the last allocation,
+        // the freed tail goes back to the arena.
         if new_size <= old_size {
+            if !self.is_shut_down() {
+                owner.shrink_in_place(ptr, old_size, new_size);
+            }
+            return ptr;
+        }
+
+        // The last allocation in an arena (e.g. a Vec that keeps pushing)
+        // can simply extend the cursor. Only the arena owning `ptr` may try;
+        // the next arena's cursor can also sit at `ptr + old_size`.
+        if !self.is_shut_down() && owner.grow_in_place(ptr, old_size, new_size) {
             return ptr;
         }
 
         // Allocate a new block
-        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
         let new_ptr = self.alloc(new_layout);
 
         if new_ptr.is_null() {
@@ -324,6 +999,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_zero_sized_layouts() {
+        let alloc = NAlloc::new();
+        let used = alloc.stats().total_used();
+        unsafe {
+            for align in [1, 8, 64, 4096] {
+                let layout = Layout::from_size_align(0, align).unwrap();
+                let ptr = alloc.alloc(layout);
+                assert_eq!(ptr as usize, align);
+                assert_eq!(alloc.alloc_zeroed(layout), ptr);
+                alloc.dealloc(ptr, layout);
+            }
+            assert_eq!(alloc.stats().total_used(), used);
+
+            // Growing out of and shrinking back to zero size.
+            let empty = Layout::from_size_align(0, 8).unwrap();
+            let ptr = alloc.realloc(alloc.alloc(empty), empty, 16);
+            assert!(alloc.get_arenas().contains(ptr));
+            ptr.write_bytes(0x11, 16);
+            let layout = Layout::from_size_align(16, 8).unwrap();
+            assert_eq!(alloc.realloc(ptr, layout, 0) as usize, 8);
+        }
+        assert_eq!(alloc.stats().total_used(), used);
+        assert_eq!(alloc.stats().system_fallbacks, 0);
+    }
+
     #[test]
     fn test_realloc() {
         let alloc = NAlloc::new();
@@ -401,6 +1102,294 @@ mod tests {
         assert!(stats_after_large.polynomial_used >= 2 * 1024 * 1024);
     }
 
+    #[test]
+    fn test_system_fallback_on_exhaustion() {
+        let alloc = NAlloc::new();
+        alloc.set_large_alloc_threshold(usize::MAX);
+
+        // Larger than the whole scratch arena, so it must fall back.
+        let layout = Layout::from_size_align(SCRATCH_ARENA_SIZE * 2, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            assert!(!ptr.is_null());
+            assert!(!alloc.get_arenas().contains(ptr));
+            assert_eq!(alloc.stats().system_fallbacks, 1);
+
+            ptr.write(0xAB);
+            let grown = alloc.realloc(ptr, layout, layout.size() + 4096);
+            assert!(!grown.is_null());
+            assert_eq!(*grown, 0xAB);
+            alloc.dealloc(
+                grown,
+                Layout::from_size_align(layout.size() + 4096, 8).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_initialized_does_not_init() {
+        let alloc = NAlloc::new();
+        assert!(!alloc.is_initialized());
+        assert!(!alloc.is_initialized());
+        alloc.stats();
+        assert!(alloc.is_initialized());
+    }
+
+    #[test]
+    fn test_preinit_reports_failure() {
+        let alloc = NAlloc::new();
+        alloc.preinit().unwrap();
+        assert!(alloc.is_initialized());
+        // A second call keeps the existing arenas.
+        let arenas = alloc.get_arenas() as *const ArenaManager;
+        alloc.preinit().unwrap();
+        assert_eq!(alloc.get_arenas() as *const ArenaManager, arenas);
+
+        // No address space is large enough for this.
+        let unreservable = usize::MAX & !(PAGE_ALIGN - 1);
+        let alloc = NAlloc::builder().witness_size(unreservable).build();
+        let err = alloc.preinit().unwrap_err();
+        assert!(err.requested_size >= unreservable);
+        assert!(!alloc.is_initialized());
+        // The failure is not sticky.
+        assert!(alloc.preinit().is_err());
+    }
+
+    #[test]
+    fn test_init_failure_returns_null() {
+        let unreservable = usize::MAX & !(PAGE_ALIGN - 1);
+        let alloc = NAlloc::builder().witness_size(unreservable).build();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        assert!(unsafe { alloc.alloc(layout) }.is_null());
+        assert!(!alloc.is_initialized());
+
+        assert!(NAlloc::builder()
+            .witness_size(unreservable)
+            .try_build()
+            .is_err());
+        assert!(NAlloc::try_new().unwrap().is_initialized());
+    }
+
+    #[test]
+    fn test_shutdown_routes_to_system() {
+        let alloc = NAlloc::new();
+        let layout = Layout::from_size_align(256, 8).unwrap();
+        unsafe {
+            let early = alloc.alloc(layout);
+            assert!(alloc.get_arenas().contains(early));
+            early.write(0x5A);
+
+            alloc.shutdown();
+            assert!(alloc.is_shut_down());
+
+            let late = alloc.alloc(layout);
+            assert!(!late.is_null());
+            assert!(!alloc.get_arenas().contains(late));
+            alloc.dealloc(late, layout);
+
+            // Earlier arena memory stays usable and moves out on growth.
+            let grown = alloc.realloc(early, layout, 512);
+            assert!(!alloc.get_arenas().contains(grown));
+            assert_eq!(*grown, 0x5A);
+            alloc.dealloc(grown, Layout::from_size_align(512, 8).unwrap());
+        }
+        assert_eq!(alloc.stats().scratch_used, 256);
+    }
+
+    #[test]
+    fn test_pressure_callback_fires_once() {
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        fn on_pressure(kind: ArenaKind, utilization: f64) {
+            assert_eq!(kind, ArenaKind::Scratch);
+            assert!(utilization >= 0.5);
+            CALLS.fetch_add(1, Ordering::Relaxed);
+        }
+
+        let alloc = NAlloc::builder().scratch_size(64 * 1024).build();
+        alloc.set_pressure_callback(0.5, Some(on_pressure));
+        let quarter = Layout::from_size_align(16 * 1024, 8).unwrap();
+        unsafe {
+            alloc.alloc(quarter);
+            assert_eq!(CALLS.load(Ordering::Relaxed), 0);
+            alloc.alloc(quarter);
+            assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+            alloc.alloc(quarter);
+            assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+
+            alloc.reset_all();
+            alloc.alloc(quarter);
+            alloc.alloc(quarter);
+        }
+        assert_eq!(CALLS.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_realloc_grows_last_allocation_in_place() {
+        let alloc = NAlloc::new();
+        let layout = Layout::from_size_align(64, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            ptr.write(0x5A);
+            let used = alloc.stats().scratch_used;
+
+            let grown = alloc.realloc(ptr, layout, 256);
+            assert_eq!(grown, ptr);
+            assert_eq!(*grown, 0x5A);
+            assert_eq!(alloc.stats().scratch_used, used + 192);
+
+            // Once something else is allocated, growth must copy.
+            let _other = alloc.alloc(layout);
+            let moved = alloc.realloc(grown, Layout::from_size_align(256, 8).unwrap(), 512);
+            assert_ne!(moved, grown);
+            assert_eq!(*moved, 0x5A);
+        }
+    }
+
+    #[test]
+    fn test_realloc_does_not_grow_past_a_full_arena() {
+        let alloc = NAlloc::builder()
+            .witness_size(64 * 1024)
+            .poly_size(64 * 1024)
+            .scratch_size(64 * 1024)
+            .build();
+        let layout = Layout::from_size_align(64 * 1024, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            assert_eq!(alloc.get_arenas().arena_of(ptr), Some(ArenaKind::Scratch));
+            assert_eq!(alloc.stats().scratch_used, 64 * 1024);
+
+            // The block ends at the arena's limit, which is often where the
+            // next mapping starts; growing it must copy instead.
+            let grown = alloc.realloc(ptr, layout, 68 * 1024);
+            assert!(!grown.is_null());
+            assert_ne!(grown, ptr);
+            let stats = alloc.stats();
+            assert_eq!(stats.witness_used, 0);
+            assert_eq!(stats.polynomial_used, 0);
+            alloc.dealloc(grown, Layout::from_size_align(68 * 1024, 8).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_realloc_shrinks_last_allocation_and_rejects_bad_sizes() {
+        let alloc = NAlloc::new();
+        let layout = Layout::from_size_align(256, 8).unwrap();
+        unsafe {
+            let ptr = alloc.alloc(layout);
+            let used = alloc.stats().scratch_used;
+
+            let shrunk = alloc.realloc(ptr, layout, 64);
+            assert_eq!(shrunk, ptr);
+            assert_eq!(alloc.stats().scratch_used, used - 192);
+
+            // Rounded up to the alignment, this size would exceed `isize::MAX`.
+            let huge = isize::MAX as usize - 2;
+            let small = Layout::from_size_align(64, 8).unwrap();
+            assert!(alloc.realloc(shrunk, small, huge).is_null());
+            assert_eq!(alloc.stats().scratch_used, used - 192);
+        }
+    }
+
+    #[test]
+    fn test_dealloc_rewinds_lifo_allocations() {
+        let alloc = NAlloc::new();
+        let layout = Layout::from_size_align(128, 8).unwrap();
+        unsafe {
+            let base = alloc.stats().scratch_used;
+            let ptr = alloc.alloc(layout);
+            alloc.dealloc(ptr, layout);
+            assert_eq!(alloc.stats().scratch_used, base);
+            assert_eq!(alloc.alloc(layout), ptr);
+        }
+    }
+
+    #[test]
+    fn test_builder_configures_lazy_init() {
+        let alloc = NAlloc::builder()
+            .witness_size(1024 * 1024)
+            .poly_size(2 * 1024 * 1024)
+            .scratch_size(3 * 1024 * 1024)
+            .large_threshold(4096)
+            .mlock_witness(true)
+            .build();
+        assert_eq!(alloc.large_alloc_threshold(), 4096);
+
+        let stats = alloc.stats();
+        assert_eq!(stats.witness_capacity, 1024 * 1024);
+        assert_eq!(stats.polynomial_capacity, 2 * 1024 * 1024);
+        assert_eq!(stats.scratch_capacity, 3 * 1024 * 1024);
+        assert!(alloc.witness().is_locked());
+
+        unsafe {
+            let ptr = alloc.alloc(Layout::from_size_align(8192, 8).unwrap());
+            assert!(!ptr.is_null());
+        }
+        assert_eq!(alloc.stats().polynomial_used, 8192);
+    }
+
+    #[test]
+    fn test_large_alloc_threshold_routing() {
+        let alloc = NAlloc::new();
+        assert_eq!(alloc.large_alloc_threshold(), LARGE_ALLOC_THRESHOLD);
+
+        alloc.set_large_alloc_threshold(128 * 1024);
+        let layout = Layout::from_size_align(256 * 1024, 64).unwrap();
+        unsafe {
+            let _ = alloc.alloc(layout);
+        }
+
+        let stats = alloc.stats();
+        assert!(stats.polynomial_used >= 256 * 1024);
+        assert_eq!(stats.scratch_used, 0);
+    }
+
+    #[test]
+    fn test_with_arena_routes_and_restores() {
+        let alloc = NAlloc::new();
+        let small = Layout::from_size_align(64, 8).unwrap();
+        let large = Layout::from_size_align(LARGE_ALLOC_THRESHOLD + 1, 64).unwrap();
+
+        alloc.with_arena(ArenaKind::Witness, || unsafe {
+            let _ = alloc.alloc(small);
+            // Nested routing wins, then the outer one comes back.
+            alloc.with_arena(ArenaKind::Scratch, || {
+                let _ = alloc.alloc(large);
+            });
+            let _ = alloc.alloc(small);
+        });
+        let stats = alloc.stats();
+        assert_eq!(stats.witness_used, 128);
+        assert!(stats.scratch_used > LARGE_ALLOC_THRESHOLD);
+        assert_eq!(stats.polynomial_used, 0);
+
+        // A panic inside `f` still restores the default strategy.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            alloc.with_arena(ArenaKind::Witness, || panic!("routed"))
+        }));
+        assert!(result.is_err());
+        unsafe {
+            let _ = alloc.alloc(small);
+        }
+        assert_eq!(alloc.stats().witness_used, 128);
+    }
+
+    #[test]
+    fn test_concurrent_init_yielding() {
+        use std::sync::Arc;
+        use std::thread;
+
+        // Waiters yield immediately instead of spinning.
+        let alloc = Arc::new(NAlloc::builder().init_spin_limit(0).build());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let alloc = Arc::clone(&alloc);
+                thread::spawn(move || alloc.scratch().capacity())
+            })
+            .collect();
+        let capacities: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert!(capacities.windows(2).all(|w| w[0] == w[1]));
+    }
+
     #[test]
     fn test_concurrent_init() {
         use std::sync::Arc;
@@ -0,0 +1,291 @@
+//! Mapped Arena for nalloc.
+//!
+//! The `MappedArena` bump-allocates from a file mapped with `MAP_SHARED`:
+//!
+//! - **Persistent**: Contents survive the process and can be reloaded.
+//! - **Resumable**: The cursor is saved in a header page, so a reopened
+//!   arena continues where the last [`sync`](MappedArena::sync) left off.
+//! - **Same cursor logic**: Backed by a regular [`BumpAlloc`].
+//!
+//! Everything in the arena ends up on disk, so it is meant for polynomial
+//! and scratch data only. It is a separate type that cannot back a
+//! [`WitnessArena`](crate::WitnessArena): never put secrets in it.
+//!
+//! Only available on Unix.
+
+use crate::bump::BumpAlloc;
+use crate::platform::sys;
+use std::fs::OpenOptions;
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Identifies a file written by [`MappedArena`] ("NALLOCMA").
+const MAPPED_MAGIC: u64 = 0x4e41_4c4c_4f43_4d41;
+
+/// Layout of the header page at the start of the file.
+#[repr(C)]
+struct Header {
+    magic: u64,
+    /// Arena capacity in bytes, excluding the header page.
+    capacity: u64,
+    /// Cursor offset at the last sync.
+    used: AtomicU64,
+}
+
+/// Arena over a memory-mapped file, for checkpointing intermediate
+/// proving state.
+///
+/// The file holds one header page followed by `capacity` bytes of arena
+/// memory. Allocations are only durable once [`sync`](Self::sync) has
+/// returned; the cursor is also saved, without flushing, on drop.
+pub struct MappedArena {
+    inner: BumpAlloc,
+    header: NonNull<Header>,
+    /// Length of the whole mapping, header page included.
+    map_len: usize,
+}
+
+impl MappedArena {
+    /// Open or create the arena file at `path` with `capacity` bytes of
+    /// arena memory.
+    ///
+    /// A new (empty) file is initialized; an existing one is reloaded with
+    /// its saved cursor. Fails with [`io::ErrorKind::InvalidData`] if the
+    /// file is not a mapped arena, or [`io::ErrorKind::InvalidInput`] if it
+    /// was created with a different capacity.
+    pub fn open(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        if capacity == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "mapped arena capacity must be non-zero",
+            ));
+        }
+        let header_len = sys::page_size();
+        let map_len = capacity
+            .checked_add(header_len)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "capacity overflows"))?;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let file_len = file.metadata()?.len();
+        let fresh = file_len == 0;
+        if fresh {
+            file.set_len(map_len as u64)?;
+        } else if file_len < header_len as u64 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "file is not a mapped arena",
+            ));
+        }
+
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                map_len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        // The mapping stays valid after the file is closed.
+        drop(file);
+
+        let base = ptr as *mut u8;
+        let header = unsafe { NonNull::new_unchecked(base as *mut Header) };
+        let unmap = || unsafe {
+            libc::munmap(ptr, map_len);
+        };
+
+        let used = if fresh {
+            unsafe {
+                header.as_ptr().write(Header {
+                    magic: MAPPED_MAGIC,
+                    capacity: capacity as u64,
+                    used: AtomicU64::new(0),
+                })
+            };
+            0
+        } else {
+            let saved = unsafe { header.as_ref() };
+            if saved.magic != MAPPED_MAGIC {
+                unmap();
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "file is not a mapped arena",
+                ));
+            }
+            if saved.capacity != capacity as u64 {
+                unmap();
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "mapped arena was created with a different capacity",
+                ));
+            }
+            if file_len != map_len as u64 {
+                // Touching pages past the end of the file would fault.
+                unmap();
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "mapped arena file was truncated",
+                ));
+            }
+            (saved.used.load(Ordering::Relaxed) as usize).min(capacity)
+        };
+
+        let inner = unsafe { BumpAlloc::new(base.add(header_len), capacity) }.with_label("mapped");
+        unsafe { inner.resume_at(used) };
+        Ok(Self {
+            inner,
+            header,
+            map_len,
+        })
+    }
+
+    /// Save the cursor and flush all dirty pages to the file.
+    ///
+    /// Blocks until the data is written back.
+    pub fn sync(&self) -> io::Result<()> {
+        self.save_cursor();
+        let ret = unsafe {
+            libc::msync(
+                self.header.as_ptr() as *mut libc::c_void,
+                self.map_len,
+                libc::MS_SYNC,
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn save_cursor(&self) {
+        let header = unsafe { self.header.as_ref() };
+        header
+            .used
+            .store(self.inner.used() as u64, Ordering::Relaxed);
+    }
+
+    /// Allocate memory with the given size and alignment.
+    ///
+    /// Returns a null pointer if the arena is exhausted. The memory is not
+    /// zero-initialized.
+    #[inline]
+    pub fn alloc(&self, size: usize, align: usize) -> *mut u8 {
+        self.inner.alloc(size, align)
+    }
+
+    /// Allocate zero-initialized memory.
+    #[inline]
+    pub fn alloc_zeroed(&self, size: usize, align: usize) -> *mut u8 {
+        self.inner.alloc_zeroed(size, align)
+    }
+
+    /// Reset the arena. The file keeps its old contents until overwritten.
+    ///
+    /// # Safety
+    /// All previously allocated memory becomes invalid.
+    #[inline]
+    pub unsafe fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// Access the underlying bump allocator, e.g. for its statistics.
+    #[inline]
+    pub fn arena(&self) -> &BumpAlloc {
+        &self.inner
+    }
+
+    /// Get a pointer to the start of the arena memory.
+    ///
+    /// Allocations made in a previous run live at the same offsets from it.
+    #[inline]
+    pub fn base_ptr(&self) -> *mut u8 {
+        self.inner.base_ptr()
+    }
+
+    /// Get the number of bytes currently allocated.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.inner.used()
+    }
+
+    /// Get the remaining capacity in bytes.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Get the arena capacity, excluding the header page.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Get the `[base, limit)` addresses of the arena memory, excluding
+    /// the header page.
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        self.inner.region()
+    }
+}
+
+impl Drop for MappedArena {
+    fn drop(&mut self) {
+        // The kernel writes the dirty pages back eventually; only `sync`
+        // guarantees it.
+        self.save_cursor();
+        unsafe {
+            libc::munmap(self.header.as_ptr() as *mut libc::c_void, self.map_len);
+        }
+    }
+}
+
+// Safety: the mapping is owned by the arena, and after `open` the header is
+// only written through its atomic cursor field.
+unsafe impl Send for MappedArena {}
+unsafe impl Sync for MappedArena {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reopen_resumes_cursor() {
+        let path = std::env::temp_dir().join(format!("nalloc-mapped-{}", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let offset = {
+            let arena = MappedArena::open(&path, 64 * 1024).unwrap();
+            assert_eq!(arena.used(), 0);
+            let ptr = arena.alloc(16, 8);
+            unsafe { std::ptr::copy_nonoverlapping(b"resumable proofs".as_ptr(), ptr, 16) };
+            arena.sync().unwrap();
+            ptr as usize - arena.base_ptr() as usize
+        };
+
+        let arena = MappedArena::open(&path, 64 * 1024).unwrap();
+        assert_eq!(arena.used(), offset + 16);
+        let data = unsafe { std::slice::from_raw_parts(arena.base_ptr().add(offset), 16) };
+        assert_eq!(data, b"resumable proofs");
+        // New allocations follow the reloaded data.
+        assert!(arena.alloc(8, 8) as usize >= arena.base_ptr() as usize + offset + 16);
+        drop(arena);
+
+        let err = MappedArena::open(&path, 128 * 1024).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
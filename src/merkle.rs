@@ -0,0 +1,168 @@
+//! Merkle Arena for nalloc.
+//!
+//! The `MerkleArena` holds the node arrays of a Merkle commitment, as
+//! built during STARK/FRI hashing:
+//!
+//! - **Level-contiguous**: Each level's nodes are one packed block.
+//! - **Back to back**: Levels follow each other, 64-byte aligned.
+//! - **Rewind per tree**: Resetting the tree is a single cursor store.
+
+use crate::bump::{BumpAlloc, BumpError, GrowDirection, Marker};
+use crate::config::CACHE_LINE_ALIGN;
+use crate::sync::Mutex;
+use std::sync::Arc;
+
+/// Specialized handle for Merkle tree nodes, allocated level by level.
+///
+/// Remembers the cursor of the underlying arena at creation, and the
+/// address of every level allocated since.
+/// [`reset_tree`](Self::reset_tree) rewinds to the creation point,
+/// discarding all levels. Nothing else should allocate from that arena in
+/// between, as it would be discarded too. Use
+/// [`NAlloc::merkle`](crate::NAlloc::merkle) for a handle over a private
+/// region.
+///
+/// The arena must grow [up](GrowDirection::Up) and must not be growable,
+/// so that levels follow the creation point in address order.
+pub struct MerkleArena {
+    inner: Arc<BumpAlloc>,
+    /// Cursor position to rewind to between trees.
+    start: Marker,
+    /// Address of the cursor at creation, where the first level begins.
+    start_addr: usize,
+    /// Address of each level allocated so far, in allocation order.
+    levels: Mutex<Vec<usize>>,
+}
+
+impl MerkleArena {
+    /// Create a `MerkleArena` starting at the current cursor of `inner`.
+    #[inline]
+    pub fn new(inner: Arc<BumpAlloc>) -> Self {
+        debug_assert_eq!(
+            inner.direction(),
+            GrowDirection::Up,
+            "merkle arena must grow up"
+        );
+        #[cfg(feature = "growable")]
+        debug_assert!(!inner.is_growable(), "merkle arena must not be growable");
+        let start = inner.checkpoint();
+        let start_addr = inner.base_ptr() as usize + inner.used();
+        Self {
+            inner,
+            start,
+            start_addr,
+            levels: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Allocate the next level: `node_count` nodes of `node_bytes` bytes
+    /// each, packed back to back behind a 64-byte aligned base.
+    ///
+    /// Returns a null pointer if the arena is exhausted or the size
+    /// overflows; no level is recorded then.
+    pub fn alloc_level(&self, node_count: usize, node_bytes: usize) -> *mut u8 {
+        debug_assert!(node_count > 0);
+        debug_assert!(node_bytes > 0);
+        let Some(size) = node_count.checked_mul(node_bytes) else {
+            return std::ptr::null_mut();
+        };
+        // Held across the allocation so levels are recorded in address order.
+        let mut levels = self.levels.lock();
+        let ptr = self.inner.alloc(size, CACHE_LINE_ALIGN);
+        if !ptr.is_null() {
+            levels.push(ptr as usize);
+        }
+        ptr
+    }
+
+    /// Get the number of levels allocated since creation or the last
+    /// [`reset_tree`](Self::reset_tree).
+    #[inline]
+    pub fn level_count(&self) -> usize {
+        self.levels.lock().len()
+    }
+
+    /// Get a pointer to level `index`, counting in allocation order, or
+    /// `None` if no such level was allocated.
+    #[inline]
+    pub fn level(&self, index: usize) -> Option<*mut u8> {
+        self.levels.lock().get(index).map(|&addr| addr as *mut u8)
+    }
+
+    /// Get the offset of every level from the start of the tree, in
+    /// allocation order, e.g. to traverse the tree from serialized form.
+    pub fn level_offsets(&self) -> Vec<usize> {
+        self.levels
+            .lock()
+            .iter()
+            .map(|&addr| addr - self.start_addr)
+            .collect()
+    }
+
+    /// Rewind to where the handle was created, e.g. before the next tree.
+    ///
+    /// Fails with [`BumpError::StaleMarker`] if the underlying arena was
+    /// reset in the meantime; the levels are forgotten either way.
+    ///
+    /// # Safety
+    /// Every level allocated from this handle becomes invalid.
+    pub unsafe fn reset_tree(&self) -> Result<(), BumpError> {
+        let mut levels = self.levels.lock();
+        levels.clear();
+        self.inner.restore(self.start)
+    }
+
+    /// Get the number of bytes the levels currently occupy, including
+    /// alignment padding.
+    #[inline]
+    pub fn used(&self) -> usize {
+        (self.inner.base_ptr() as usize + self.inner.used()).saturating_sub(self.start_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NAlloc;
+
+    #[test]
+    fn test_levels_are_contiguous() {
+        let alloc = NAlloc::new();
+        let tree = alloc.merkle(64 * 1024).unwrap();
+
+        // 256 leaves of 32-byte digests, then every level above them.
+        let mut nodes = 256;
+        while nodes > 0 {
+            let level = tree.alloc_level(nodes, 32);
+            assert!(!level.is_null());
+            assert_eq!(level as usize % CACHE_LINE_ALIGN, 0);
+            assert_eq!(tree.level(tree.level_count() - 1), Some(level));
+            nodes /= 2;
+        }
+        assert_eq!(tree.level_count(), 9);
+        let offsets = tree.level_offsets();
+        assert_eq!(&offsets[..3], &[0, 256 * 32, 256 * 32 + 128 * 32]);
+        assert_eq!(tree.used(), offsets[8] + 32);
+
+        assert!(tree.alloc_level(usize::MAX, 2).is_null());
+        assert!(tree.alloc_level(4096, 32).is_null());
+        assert_eq!(tree.level_count(), 9);
+
+        let first = tree.level(0).unwrap();
+        unsafe { tree.reset_tree().unwrap() };
+        assert_eq!(tree.used(), 0);
+        assert_eq!(tree.level_count(), 0);
+        assert!(tree.level(0).is_none());
+        assert_eq!(tree.alloc_level(256, 32), first);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "must grow up")]
+    fn test_rejects_downward_arena() {
+        let mut buffer = vec![0u8; 4096];
+        let arena = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) }
+            .with_direction(GrowDirection::Down);
+        let _ = MerkleArena::new(Arc::new(arena));
+    }
+}
@@ -0,0 +1,126 @@
+//! Pinned Arena for nalloc.
+//!
+//! The `PinnedArena` bump-allocates from page-locked host memory obtained
+//! from the CUDA runtime:
+//!
+//! - **DMA-ready**: Host-device copies skip the driver's staging buffer.
+//! - **Same cursor logic**: Backed by a regular [`BumpAlloc`].
+//! - **Owned region**: Freed with `cudaFreeHost` on drop.
+//!
+//! Requires the `cuda` feature and links against `cudart`.
+
+use crate::bump::BumpAlloc;
+use crate::platform::AllocFailed;
+use std::ffi::c_void;
+use std::ptr::NonNull;
+
+/// `cudaHostAllocDefault`: plain page-locked memory.
+const CUDA_HOST_ALLOC_DEFAULT: u32 = 0;
+
+#[link(name = "cudart")]
+extern "C" {
+    fn cudaHostAlloc(ptr: *mut *mut c_void, size: usize, flags: u32) -> i32;
+    fn cudaFreeHost(ptr: *mut c_void) -> i32;
+}
+
+/// Arena over CUDA pinned host memory, for buffers copied to and from the
+/// GPU (e.g. MSM bases and NTT inputs).
+///
+/// Pinned memory cannot be swapped or moved, so pin only what the
+/// transfers need: a large pinned region reduces the memory left to the
+/// rest of the system.
+pub struct PinnedArena {
+    inner: BumpAlloc,
+}
+
+impl PinnedArena {
+    /// Allocate a `size`-byte pinned region with `cudaHostAlloc`.
+    ///
+    /// On failure the CUDA error code is reported in
+    /// [`AllocFailed::error_code`].
+    pub fn new(size: usize) -> Result<Self, AllocFailed> {
+        if size == 0 {
+            return Err(AllocFailed::new(size));
+        }
+        let mut ptr = std::ptr::null_mut();
+        let status = unsafe { cudaHostAlloc(&mut ptr, size, CUDA_HOST_ALLOC_DEFAULT) };
+        if status != 0 || ptr.is_null() {
+            return Err(AllocFailed {
+                requested_size: size,
+                error_code: Some(status),
+            });
+        }
+        Ok(Self {
+            inner: unsafe { BumpAlloc::new(ptr as *mut u8, size) }.with_label("pinned"),
+        })
+    }
+
+    /// Allocate pinned memory with the given size and alignment.
+    ///
+    /// Returns a null pointer if the arena is exhausted. The memory is not
+    /// zero-initialized.
+    #[inline]
+    pub fn alloc(&self, size: usize, align: usize) -> *mut u8 {
+        self.inner.alloc(size, align)
+    }
+
+    /// Like [`alloc`](Self::alloc), but returns `None` instead of a null
+    /// pointer when the arena is exhausted.
+    #[inline]
+    pub fn alloc_nn(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        self.inner.alloc_nn(size, align)
+    }
+
+    /// Allocate zero-initialized pinned memory.
+    #[inline]
+    pub fn alloc_zeroed(&self, size: usize, align: usize) -> *mut u8 {
+        self.inner.alloc_zeroed(size, align)
+    }
+
+    /// Reset the pinned arena; the region stays pinned.
+    ///
+    /// # Safety
+    /// All previously allocated pinned memory becomes invalid, and no
+    /// transfer may still be reading or writing it.
+    #[inline]
+    pub unsafe fn reset(&self) {
+        self.inner.reset();
+    }
+
+    /// Access the underlying bump allocator, e.g. for its statistics.
+    #[inline]
+    pub fn arena(&self) -> &BumpAlloc {
+        &self.inner
+    }
+
+    /// Get the number of bytes currently allocated.
+    #[inline]
+    pub fn used(&self) -> usize {
+        self.inner.used()
+    }
+
+    /// Get the remaining capacity in bytes.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Get the total size of the pinned region.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Get the `[base, limit)` addresses of the pinned region.
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        self.inner.region()
+    }
+}
+
+impl Drop for PinnedArena {
+    fn drop(&mut self) {
+        // Best-effort, like the OS-backed arenas.
+        let _ = unsafe { cudaFreeHost(self.inner.base_ptr() as *mut c_void) };
+    }
+}
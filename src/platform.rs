@@ -6,11 +6,13 @@
 //! - **macOS**: `mach_vm_allocate` via `mach2`
 //! - **Windows**: `VirtualAlloc` via `windows-sys`
 //! - **Other Unix**: `mmap` via `libc`
+//! - **WebAssembly**: `memory.grow` on the linear memory
 
 use std::fmt;
 
 /// Error type for system memory allocation failures.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AllocFailed {
     /// The size that was requested.
     pub requested_size: usize,
@@ -55,13 +57,30 @@ impl AllocFailed {
     }
 }
 
+/// Memory access permissions for [`sys::protect`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protection {
+    /// Any access faults.
+    None,
+    /// Reads are allowed; writes fault.
+    Read,
+    /// Reads and writes are allowed.
+    ReadWrite,
+}
+
+/// NUMA placement for [`sys::bind_numa`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumaPolicy {
+    /// Place all pages on this node.
+    Bind(u32),
+    /// Spread pages round-robin over the nodes set in this mask
+    /// (bit `n` is node `n`).
+    Interleave(u64),
+}
+
 /// Platform-specific memory allocation functions.
 pub mod sys {
-    use super::AllocFailed;
-
-    // ========================================================================
-    // Linux Implementation (using rustix)
-    // ========================================================================
+    use super::{AllocFailed, NumaPolicy, Protection};
 
     /// Allocate `size` bytes of virtual memory from the OS.
     ///
@@ -69,27 +88,85 @@ pub mod sys {
     /// - Read/Write accessible
     /// - Not backed by physical pages until touched (on most OSes)
     /// - Aligned to at least the system page size
-    #[cfg(target_os = "linux")]
     #[inline]
     pub fn alloc(size: usize) -> Result<*mut u8, AllocFailed> {
+        alloc_at(std::ptr::null_mut(), size)
+    }
+
+    // ========================================================================
+    // Linux Implementation (using rustix)
+    // ========================================================================
+
+    /// Allocate like [`alloc`], asking the OS to place the mapping at `hint`.
+    ///
+    /// The hint is only a request (no `MAP_FIXED`): if the range is taken
+    /// or unusable the OS picks another address, and existing mappings are
+    /// never replaced. Useful for reproducible layouts across runs.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn alloc_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
+        use rustix::mm::{mmap_anonymous, MapFlags, ProtFlags};
+
+        debug_assert!(size > 0);
+
+        unsafe {
+            match mmap_anonymous(
+                hint.cast(),
+                size,
+                ProtFlags::READ | ProtFlags::WRITE,
+                MapFlags::PRIVATE | MapFlags::NORESERVE,
+            ) {
+                Ok(ptr) => Ok(ptr as *mut u8),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Allocate `size` bytes backed by explicit huge pages (`MAP_HUGETLB`).
+    ///
+    /// `page_size` must be a power of two supported by the kernel (typically
+    /// 2MB or 1GB), and `size` must be a multiple of it. Fails if no huge
+    /// pages of that size are reserved (see `/proc/sys/vm/nr_hugepages`).
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn alloc_huge_pages(size: usize, page_size: usize) -> Result<*mut u8, AllocFailed> {
         use rustix::mm::{mmap_anonymous, MapFlags, ProtFlags};
         use std::ptr;
 
+        /// Bit offset of the log2 page size in the mmap flags (`MAP_HUGE_SHIFT`).
+        const MAP_HUGE_SHIFT: u32 = 26;
+
         debug_assert!(size > 0);
+        debug_assert!(page_size.is_power_of_two());
+        debug_assert_eq!(size % page_size, 0);
+
+        let page_size_flag =
+            MapFlags::from_bits_retain(page_size.trailing_zeros() << MAP_HUGE_SHIFT);
 
         unsafe {
             match mmap_anonymous(
                 ptr::null_mut(),
                 size,
                 ProtFlags::READ | ProtFlags::WRITE,
-                MapFlags::PRIVATE | MapFlags::NORESERVE,
+                // No NORESERVE here: the kernel must reserve the huge pages up
+                // front so a shortage fails the mmap instead of raising SIGBUS
+                // on first touch.
+                MapFlags::PRIVATE | MapFlags::HUGETLB | page_size_flag,
             ) {
                 Ok(ptr) => Ok(ptr as *mut u8),
-                Err(_) => Err(AllocFailed::new(size)),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
             }
         }
     }
 
+    /// Explicit huge pages are only supported on Linux and Windows; always
+    /// fails elsewhere.
+    #[cfg(not(any(target_os = "linux", target_os = "windows")))]
+    #[inline]
+    pub fn alloc_huge_pages(size: usize, _page_size: usize) -> Result<*mut u8, AllocFailed> {
+        Err(AllocFailed::new(size))
+    }
+
     /// Deallocate memory previously allocated with `alloc`.
     #[cfg(target_os = "linux")]
     #[inline]
@@ -103,18 +180,183 @@ pub mod sys {
         unsafe {
             match munmap(ptr as *mut _, size) {
                 Ok(()) => Ok(()),
-                Err(_) => Err(AllocFailed::new(size)),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Lock `[ptr, ptr+size)` into physical memory so it is never swapped out.
+    ///
+    /// Fails if the process exceeds its locked-memory limit (e.g. `RLIMIT_MEMLOCK`).
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub unsafe fn lock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        use rustix::mm::mlock;
+
+        unsafe {
+            match mlock(ptr as *mut _, size) {
+                Ok(()) => Ok(()),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Unlock memory previously locked with `lock`.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub unsafe fn unlock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        use rustix::mm::munlock;
+
+        unsafe {
+            match munlock(ptr as *mut _, size) {
+                Ok(()) => Ok(()),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Change the access permissions of `[ptr, ptr+size)`.
+    ///
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// no reference into it may be used in a way the new access forbids.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub unsafe fn protect(
+        ptr: *mut u8,
+        size: usize,
+        access: Protection,
+    ) -> Result<(), AllocFailed> {
+        use rustix::mm::{mprotect, MprotectFlags};
+
+        let flags = match access {
+            Protection::None => MprotectFlags::empty(),
+            Protection::Read => MprotectFlags::READ,
+            Protection::ReadWrite => MprotectFlags::READ | MprotectFlags::WRITE,
+        };
+
+        unsafe {
+            match mprotect(ptr as *mut _, size, flags) {
+                Ok(()) => Ok(()),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Drop the physical pages backing `[ptr, ptr+size)` (`MADV_DONTNEED`).
+    ///
+    /// The mapping stays valid; the pages read back as zero and are
+    /// faulted in again on next touch. `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// nothing in it may still be in use: its contents are discarded.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub unsafe fn release(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        use rustix::mm::{madvise, Advice};
+
+        unsafe {
+            match madvise(ptr as *mut _, size, Advice::LinuxDontNeed) {
+                Ok(()) => Ok(()),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
+            }
+        }
+    }
+
+    /// Ask the kernel to back `[ptr, ptr+size)` with transparent huge pages
+    /// (`MADV_HUGEPAGE`).
+    ///
+    /// Needs no reserved huge pages: the kernel coalesces the range into
+    /// 2MB pages opportunistically. Fails if THP is compiled out or set to
+    /// `never`. `ptr` must be page-aligned.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn advise_huge(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        use rustix::mm::{madvise, Advice};
+
+        unsafe {
+            match madvise(ptr as *mut _, size, Advice::LinuxHugepage) {
+                Ok(()) => Ok(()),
+                Err(err) => Err(AllocFailed::with_code(size, err.raw_os_error())),
             }
         }
     }
 
+    /// Transparent huge pages are Linux-only; a no-op elsewhere.
+    #[cfg(not(target_os = "linux"))]
+    #[inline]
+    pub fn advise_huge(_ptr: *mut u8, _size: usize) -> Result<(), AllocFailed> {
+        Ok(())
+    }
+
+    /// Set the NUMA policy of `[ptr, ptr+size)` with `mbind`.
+    ///
+    /// Pages already faulted in are not moved, so call this before the
+    /// memory is touched. Only nodes 0 to 63 can be addressed. Fails if the
+    /// kernel has no NUMA support or the node does not exist.
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn bind_numa(ptr: *mut u8, size: usize, policy: NumaPolicy) -> Result<(), AllocFailed> {
+        const MPOL_BIND: libc::c_long = 2;
+        const MPOL_INTERLEAVE: libc::c_long = 3;
+
+        let (mode, mask) = match policy {
+            NumaPolicy::Bind(node) if node < 64 => (MPOL_BIND, 1u64 << node),
+            NumaPolicy::Bind(_) => return Err(AllocFailed::with_code(size, libc::EINVAL)),
+            NumaPolicy::Interleave(mask) => (MPOL_INTERLEAVE, mask),
+        };
+        // The kernel reads `maxnode - 1` bits of the mask as an array of
+        // `c_ulong`, which is only 32 bits wide on 32-bit targets.
+        const WORD_BITS: usize = libc::c_ulong::BITS as usize;
+        let mut words: [libc::c_ulong; 64 / WORD_BITS] = [0; 64 / WORD_BITS];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = (mask >> (i * WORD_BITS)) as libc::c_ulong;
+        }
+
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_mbind,
+                ptr as usize,
+                size,
+                mode,
+                words.as_ptr(),
+                64 + 1 as libc::c_ulong,
+                0 as libc::c_uint,
+            )
+        };
+        if ret == 0 {
+            Ok(())
+        } else {
+            let code = std::io::Error::last_os_error().raw_os_error().unwrap_or(0);
+            Err(AllocFailed::with_code(size, code))
+        }
+    }
+
+    /// NUMA placement is only supported on Linux; always fails elsewhere.
+    #[cfg(not(target_os = "linux"))]
+    #[inline]
+    pub fn bind_numa(_ptr: *mut u8, size: usize, _policy: NumaPolicy) -> Result<(), AllocFailed> {
+        Err(AllocFailed::new(size))
+    }
+
     // ========================================================================
     // macOS Implementation (using mach2)
     // ========================================================================
 
+    /// With `VM_FLAGS_ANYWHERE` the kernel takes `hint` as the address to
+    /// start searching from.
     #[cfg(target_vendor = "apple")]
     #[inline]
-    pub fn alloc(size: usize) -> Result<*mut u8, AllocFailed> {
+    pub fn alloc_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
         use mach2::kern_return::KERN_SUCCESS;
         use mach2::traps::mach_task_self;
         use mach2::vm::mach_vm_allocate;
@@ -124,7 +366,7 @@ pub mod sys {
         debug_assert!(size > 0);
 
         let task = unsafe { mach_task_self() };
-        let mut address: mach_vm_address_t = 0;
+        let mut address: mach_vm_address_t = hint as mach_vm_address_t;
         let vm_size: mach_vm_size_t = size as mach_vm_size_t;
 
         let retval = unsafe { mach_vm_allocate(task, &mut address, vm_size, VM_FLAGS_ANYWHERE) };
@@ -159,20 +401,147 @@ pub mod sys {
         }
     }
 
+    /// Lock `[ptr, ptr+size)` into physical memory so it is never swapped out.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_vendor = "apple")]
+    #[inline]
+    pub unsafe fn lock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::mlock(ptr as *const _, size) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Unlock memory previously locked with `lock`.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_vendor = "apple")]
+    #[inline]
+    pub unsafe fn unlock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::munlock(ptr as *const _, size) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Change the access permissions of `[ptr, ptr+size)`.
+    ///
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// no reference into it may be used in a way the new access forbids.
+    #[cfg(target_vendor = "apple")]
+    #[inline]
+    pub unsafe fn protect(
+        ptr: *mut u8,
+        size: usize,
+        access: Protection,
+    ) -> Result<(), AllocFailed> {
+        let prot = match access {
+            Protection::None => libc::PROT_NONE,
+            Protection::Read => libc::PROT_READ,
+            Protection::ReadWrite => libc::PROT_READ | libc::PROT_WRITE,
+        };
+
+        let result = unsafe { libc::mprotect(ptr as *mut _, size, prot) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Let the OS reclaim the physical pages backing `[ptr, ptr+size)` (`MADV_FREE`).
+    ///
+    /// The mapping stays valid, but the contents are undefined afterwards.
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// nothing in it may still be in use: its contents are discarded.
+    #[cfg(target_vendor = "apple")]
+    #[inline]
+    pub unsafe fn release(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::madvise(ptr as *mut _, size, libc::MADV_FREE) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
     // ========================================================================
     // Windows Implementation
     // ========================================================================
 
+    /// `VirtualAlloc` fails outright when `hint` is unavailable, so the
+    /// allocation is then retried at an address of the OS's choosing.
     #[cfg(target_os = "windows")]
     #[inline]
-    pub fn alloc(size: usize) -> Result<*mut u8, AllocFailed> {
+    pub fn alloc_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
+        use std::ptr;
+
+        const MEM_COMMIT: u32 = 0x00001000;
+        const MEM_RESERVE: u32 = 0x00002000;
+        const PAGE_READWRITE: u32 = 0x04;
+
+        extern "system" {
+            fn VirtualAlloc(
+                lpAddress: *mut u8,
+                dwSize: usize,
+                flAllocationType: u32,
+                flProtect: u32,
+            ) -> *mut u8;
+        }
+
+        debug_assert!(size > 0);
+
+        let alloc = |address| unsafe {
+            VirtualAlloc(address, size, MEM_COMMIT | MEM_RESERVE, PAGE_READWRITE)
+        };
+        let mut result = alloc(hint);
+        if result.is_null() && !hint.is_null() {
+            result = alloc(ptr::null_mut());
+        }
+
+        if result.is_null() {
+            Err(AllocFailed::new(size))
+        } else {
+            Ok(result)
+        }
+    }
+
+    /// Allocate `size` bytes backed by large pages (`MEM_LARGE_PAGES`).
+    ///
+    /// Windows has a single large-page size (`GetLargePageMinimum`, usually
+    /// 2MB); `size` is rounded up to a multiple of it and `page_size` is
+    /// ignored. Requires `SeLockMemoryPrivilege` to be held and enabled;
+    /// without it the call fails with `ERROR_PRIVILEGE_NOT_HELD`.
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub fn alloc_huge_pages(size: usize, _page_size: usize) -> Result<*mut u8, AllocFailed> {
         use std::ptr;
 
         const MEM_COMMIT: u32 = 0x00001000;
         const MEM_RESERVE: u32 = 0x00002000;
+        const MEM_LARGE_PAGES: u32 = 0x20000000;
         const PAGE_READWRITE: u32 = 0x04;
 
         extern "system" {
+            fn GetLargePageMinimum() -> usize;
+            fn GetLastError() -> u32;
             fn VirtualAlloc(
                 lpAddress: *mut u8,
                 dwSize: usize,
@@ -183,15 +552,69 @@ pub mod sys {
 
         debug_assert!(size > 0);
 
+        let large_page = unsafe { GetLargePageMinimum() };
+        if large_page == 0 {
+            return Err(AllocFailed::new(size));
+        }
+        let size = size
+            .checked_next_multiple_of(large_page)
+            .ok_or(AllocFailed::new(size))?;
+
         let result = unsafe {
             VirtualAlloc(
                 ptr::null_mut(),
                 size,
-                MEM_COMMIT | MEM_RESERVE,
+                MEM_COMMIT | MEM_RESERVE | MEM_LARGE_PAGES,
                 PAGE_READWRITE,
             )
         };
 
+        if result.is_null() {
+            Err(AllocFailed::with_code(size, unsafe { GetLastError() } as i32))
+        } else {
+            Ok(result)
+        }
+    }
+
+    /// Reserve `size` bytes of address space without committing it
+    /// (`MEM_RESERVE`).
+    ///
+    /// Unlike [`alloc`], this does not charge the commit limit. The pages
+    /// fault on access until [`commit`] is called on them. Release with
+    /// [`dealloc`].
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub fn reserve(size: usize) -> Result<*mut u8, AllocFailed> {
+        reserve_at(std::ptr::null_mut(), size)
+    }
+
+    /// Reserve like [`reserve`], preferring an address of `hint`; see
+    /// [`alloc_at`].
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub fn reserve_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
+        use std::ptr;
+
+        const MEM_RESERVE: u32 = 0x00002000;
+        const PAGE_READWRITE: u32 = 0x04;
+
+        extern "system" {
+            fn VirtualAlloc(
+                lpAddress: *mut u8,
+                dwSize: usize,
+                flAllocationType: u32,
+                flProtect: u32,
+            ) -> *mut u8;
+        }
+
+        debug_assert!(size > 0);
+
+        let reserve = |address| unsafe { VirtualAlloc(address, size, MEM_RESERVE, PAGE_READWRITE) };
+        let mut result = reserve(hint);
+        if result.is_null() && !hint.is_null() {
+            result = reserve(ptr::null_mut());
+        }
+
         if result.is_null() {
             Err(AllocFailed::new(size))
         } else {
@@ -199,6 +622,37 @@ pub mod sys {
         }
     }
 
+    /// Commit `[ptr, ptr+size)` of a range obtained from [`reserve`]
+    /// (`MEM_COMMIT`), making it readable and writable.
+    ///
+    /// Committing pages that are already committed is harmless.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a range obtained from [`reserve`].
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub unsafe fn commit(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        const MEM_COMMIT: u32 = 0x00001000;
+        const PAGE_READWRITE: u32 = 0x04;
+
+        extern "system" {
+            fn VirtualAlloc(
+                lpAddress: *mut u8,
+                dwSize: usize,
+                flAllocationType: u32,
+                flProtect: u32,
+            ) -> *mut u8;
+        }
+
+        let result = unsafe { VirtualAlloc(ptr as *mut _, size, MEM_COMMIT, PAGE_READWRITE) };
+
+        if result.is_null() {
+            Err(AllocFailed::new(size))
+        } else {
+            Ok(())
+        }
+    }
+
     /// Deallocate memory previously allocated with `alloc`.
     #[cfg(target_os = "windows")]
     #[inline]
@@ -223,11 +677,134 @@ pub mod sys {
         }
     }
 
-    // ========================================================================
-    // Unix Fallback (using libc mmap)
-    // ========================================================================
-
-    /// Fallback for other Unix-like systems.
+    /// Lock `[ptr, ptr+size)` into physical memory so it is never paged out.
+    ///
+    /// Fails if the size exceeds the process working set limits.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub unsafe fn lock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        extern "system" {
+            fn VirtualLock(lpAddress: *mut u8, dwSize: usize) -> i32;
+        }
+
+        let result = unsafe { VirtualLock(ptr, size) };
+
+        if result != 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::new(size))
+        }
+    }
+
+    /// Unlock memory previously locked with `lock`.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub unsafe fn unlock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        extern "system" {
+            fn VirtualUnlock(lpAddress: *mut u8, dwSize: usize) -> i32;
+        }
+
+        let result = unsafe { VirtualUnlock(ptr, size) };
+
+        if result != 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::new(size))
+        }
+    }
+
+    /// Change the access permissions of `[ptr, ptr+size)`.
+    ///
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// no reference into it may be used in a way the new access forbids.
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub unsafe fn protect(
+        ptr: *mut u8,
+        size: usize,
+        access: Protection,
+    ) -> Result<(), AllocFailed> {
+        const PAGE_NOACCESS: u32 = 0x01;
+        const PAGE_READONLY: u32 = 0x02;
+        const PAGE_READWRITE: u32 = 0x04;
+
+        extern "system" {
+            fn VirtualProtect(
+                lpAddress: *mut u8,
+                dwSize: usize,
+                flNewProtect: u32,
+                lpflOldProtect: *mut u32,
+            ) -> i32;
+        }
+
+        let prot = match access {
+            Protection::None => PAGE_NOACCESS,
+            Protection::Read => PAGE_READONLY,
+            Protection::ReadWrite => PAGE_READWRITE,
+        };
+
+        let mut old = 0u32;
+        let result = unsafe { VirtualProtect(ptr, size, prot, &mut old) };
+
+        if result != 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::new(size))
+        }
+    }
+
+    /// Drop the physical pages backing `[ptr, ptr+size)`.
+    ///
+    /// The range is decommitted and immediately recommitted, so it stays
+    /// usable and reads back as zero. `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// nothing in it may still be in use: its contents are discarded.
+    #[cfg(target_os = "windows")]
+    #[inline]
+    pub unsafe fn release(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        const MEM_COMMIT: u32 = 0x00001000;
+        const MEM_DECOMMIT: u32 = 0x00004000;
+        const PAGE_READWRITE: u32 = 0x04;
+
+        extern "system" {
+            fn VirtualAlloc(
+                lpAddress: *mut u8,
+                dwSize: usize,
+                flAllocationType: u32,
+                flProtect: u32,
+            ) -> *mut u8;
+            fn VirtualFree(lpAddress: *mut u8, dwSize: usize, dwFreeType: u32) -> i32;
+        }
+
+        if unsafe { VirtualFree(ptr as *mut _, size, MEM_DECOMMIT) } == 0 {
+            return Err(AllocFailed::new(size));
+        }
+
+        let result = unsafe { VirtualAlloc(ptr as *mut _, size, MEM_COMMIT, PAGE_READWRITE) };
+
+        if result.is_null() {
+            Err(AllocFailed::new(size))
+        } else {
+            Ok(())
+        }
+    }
+
+    // ========================================================================
+    // Unix Fallback (using libc mmap)
+    // ========================================================================
+
+    /// Fallback for other Unix-like systems.
     #[cfg(all(
         not(target_os = "linux"),
         not(target_vendor = "apple"),
@@ -235,15 +812,14 @@ pub mod sys {
         unix
     ))]
     #[inline]
-    pub fn alloc(size: usize) -> Result<*mut u8, AllocFailed> {
+    pub fn alloc_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
         use libc::{mmap, MAP_ANON, MAP_FAILED, MAP_PRIVATE, PROT_READ, PROT_WRITE};
-        use std::ptr;
 
         debug_assert!(size > 0);
 
         let result = unsafe {
             mmap(
-                ptr::null_mut(),
+                hint.cast(),
                 size,
                 PROT_READ | PROT_WRITE,
                 MAP_PRIVATE | MAP_ANON,
@@ -282,12 +858,501 @@ pub mod sys {
             Err(AllocFailed::new(size))
         }
     }
+
+    /// Lock `[ptr, ptr+size)` into physical memory so it is never swapped out.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(all(
+        not(target_os = "linux"),
+        not(target_vendor = "apple"),
+        not(target_os = "windows"),
+        unix
+    ))]
+    #[inline]
+    pub unsafe fn lock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::mlock(ptr as *const _, size) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Unlock memory previously locked with `lock`.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(all(
+        not(target_os = "linux"),
+        not(target_vendor = "apple"),
+        not(target_os = "windows"),
+        unix
+    ))]
+    #[inline]
+    pub unsafe fn unlock(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::munlock(ptr as *const _, size) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Change the access permissions of `[ptr, ptr+size)`.
+    ///
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// no reference into it may be used in a way the new access forbids.
+    #[cfg(all(
+        not(target_os = "linux"),
+        not(target_vendor = "apple"),
+        not(target_os = "windows"),
+        unix
+    ))]
+    #[inline]
+    pub unsafe fn protect(
+        ptr: *mut u8,
+        size: usize,
+        access: Protection,
+    ) -> Result<(), AllocFailed> {
+        let prot = match access {
+            Protection::None => libc::PROT_NONE,
+            Protection::Read => libc::PROT_READ,
+            Protection::ReadWrite => libc::PROT_READ | libc::PROT_WRITE,
+        };
+
+        let result = unsafe { libc::mprotect(ptr as *mut _, size, prot) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    /// Let the OS reclaim the physical pages backing `[ptr, ptr+size)` (`MADV_FREE`).
+    ///
+    /// The mapping stays valid, but the contents are undefined afterwards.
+    /// `ptr` must be page-aligned.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// nothing in it may still be in use: its contents are discarded.
+    #[cfg(all(
+        not(target_os = "linux"),
+        not(target_vendor = "apple"),
+        not(target_os = "windows"),
+        unix
+    ))]
+    #[inline]
+    pub unsafe fn release(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        let result = unsafe { libc::madvise(ptr as *mut _, size, libc::MADV_FREE) };
+
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(AllocFailed::with_code(size, errno()))
+        }
+    }
+
+    // ========================================================================
+    // WebAssembly Implementation (using memory.grow)
+    // ========================================================================
+
+    /// Size of a WebAssembly linear memory page.
+    #[cfg(target_arch = "wasm32")]
+    const WASM_PAGE: usize = 64 * 1024;
+
+    /// Grow the linear memory by enough 64KB pages to hold `size` bytes.
+    ///
+    /// New pages are zeroed. The memory is never returned: linear memory
+    /// cannot shrink.
+    ///
+    /// Linear memory only grows at its end, so `hint` is ignored.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub fn alloc_at(_hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
+        debug_assert!(size > 0);
+
+        let pages = size.div_ceil(WASM_PAGE);
+        match core::arch::wasm32::memory_grow(0, pages) {
+            usize::MAX => Err(AllocFailed::new(size)),
+            previous => Ok((previous * WASM_PAGE) as *mut u8),
+        }
+    }
+
+    /// No-op: linear memory cannot shrink, so the pages stay allocated.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub fn dealloc(_ptr: *mut u8, _size: usize) -> Result<(), AllocFailed> {
+        Ok(())
+    }
+
+    /// No-op: linear memory is never swapped out by the engine.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub unsafe fn lock(_ptr: *mut u8, _size: usize) -> Result<(), AllocFailed> {
+        Ok(())
+    }
+
+    /// No-op counterpart of `lock`.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub unsafe fn unlock(_ptr: *mut u8, _size: usize) -> Result<(), AllocFailed> {
+        Ok(())
+    }
+
+    /// Always fails: linear memory has no access permissions.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// no reference into it may be used in a way the new access forbids.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub unsafe fn protect(
+        _ptr: *mut u8,
+        size: usize,
+        access: Protection,
+    ) -> Result<(), AllocFailed> {
+        match access {
+            Protection::ReadWrite => Ok(()),
+            Protection::None | Protection::Read => Err(AllocFailed::new(size)),
+        }
+    }
+
+    /// No-op: linear memory pages cannot be handed back.
+    ///
+    /// # Safety
+    /// `[ptr, ptr+size)` must lie within a mapping owned by the caller, and
+    /// nothing in it may still be in use: its contents are discarded.
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub unsafe fn release(_ptr: *mut u8, _size: usize) -> Result<(), AllocFailed> {
+        Ok(())
+    }
+
+    // ========================================================================
+    // Guard Pages (all platforms)
+    // ========================================================================
+
+    /// Allocate `size` bytes with an inaccessible guard page on each side.
+    ///
+    /// Any access just before or just past the returned region faults
+    /// immediately instead of silently corrupting neighbouring memory.
+    /// If `size` is not a multiple of [`page_size`], the trailing guard
+    /// starts at the next page boundary past the region.
+    /// Release with [`dealloc_guarded`].
+    #[inline]
+    pub fn alloc_guarded(size: usize) -> Result<*mut u8, AllocFailed> {
+        alloc_guarded_at(core::ptr::null_mut(), size)
+    }
+
+    /// Allocate like [`alloc_guarded`], asking for the leading guard page to
+    /// be placed at `hint`; see [`alloc_at`].
+    #[inline]
+    pub fn alloc_guarded_at(hint: *mut u8, size: usize) -> Result<*mut u8, AllocFailed> {
+        let page = page_size();
+        let (rounded, total) = size
+            .checked_next_multiple_of(page)
+            .and_then(|rounded| Some((rounded, rounded.checked_add(2 * page)?)))
+            .ok_or(AllocFailed::new(size))?;
+        let raw = alloc_at(hint, total)?;
+
+        // Safety: both guard pages lie within the fresh mapping.
+        let guarded = unsafe {
+            protect(raw, page, Protection::None)
+                .and_then(|()| protect(raw.add(page + rounded), page, Protection::None))
+        };
+        match guarded {
+            Ok(()) => Ok(unsafe { raw.add(page) }),
+            Err(err) => {
+                let _ = dealloc(raw, total);
+                Err(err)
+            }
+        }
+    }
+
+    /// Deallocate memory previously allocated with `alloc_guarded`.
+    #[inline]
+    pub fn dealloc_guarded(ptr: *mut u8, size: usize) -> Result<(), AllocFailed> {
+        if ptr.is_null() {
+            return Ok(());
+        }
+        let page = page_size();
+        let rounded = size.div_ceil(page) * page;
+        dealloc(ptr.wrapping_sub(page), rounded + 2 * page)
+    }
+
+    // ========================================================================
+    // Environment (all platforms)
+    // ========================================================================
+
+    /// Read environment variable `name` into `buf` without allocating.
+    ///
+    /// Safe to call while the arenas backing a global allocator are being
+    /// initialized, where `std::env::var` would recurse into the allocator.
+    /// Returns `None` if the variable is unset or does not fit in `buf`.
+    pub fn env_var<'a>(name: &std::ffi::CStr, buf: &'a mut [u8]) -> Option<&'a [u8]> {
+        #[cfg(unix)]
+        {
+            let value = unsafe { libc::getenv(name.as_ptr()) };
+            if value.is_null() {
+                return None;
+            }
+            let bytes = unsafe { std::ffi::CStr::from_ptr(value) }.to_bytes();
+            let out = buf.get_mut(..bytes.len())?;
+            out.copy_from_slice(bytes);
+            Some(out)
+        }
+
+        #[cfg(windows)]
+        {
+            extern "system" {
+                fn GetEnvironmentVariableA(name: *const u8, buffer: *mut u8, size: u32) -> u32;
+            }
+            let size = u32::try_from(buf.len()).unwrap_or(u32::MAX);
+            let len = unsafe {
+                GetEnvironmentVariableA(name.as_ptr() as *const u8, buf.as_mut_ptr(), size)
+            };
+            // 0 means unset; a length >= size means the buffer was too small.
+            if len == 0 || len >= size {
+                return None;
+            }
+            Some(&buf[..len as usize])
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        {
+            let _ = (name, buf);
+            None
+        }
+    }
+
+    /// Write `bytes` to stderr without allocating or formatting.
+    ///
+    /// For diagnostics from code that may run inside the global allocator,
+    /// where `eprintln!` could recurse into it. Errors are ignored.
+    pub fn write_stderr(bytes: &[u8]) {
+        #[cfg(unix)]
+        {
+            let mut rest = bytes;
+            while !rest.is_empty() {
+                let written = unsafe {
+                    libc::write(libc::STDERR_FILENO, rest.as_ptr() as *const _, rest.len())
+                };
+                if written <= 0 {
+                    return;
+                }
+                rest = &rest[written as usize..];
+            }
+        }
+
+        #[cfg(windows)]
+        {
+            const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+            extern "system" {
+                fn GetStdHandle(std_handle: u32) -> *mut u8;
+                fn WriteFile(
+                    file: *mut u8,
+                    buffer: *const u8,
+                    len: u32,
+                    written: *mut u32,
+                    overlapped: *mut u8,
+                ) -> i32;
+            }
+            let handle = unsafe { GetStdHandle(STD_ERROR_HANDLE) };
+            // Null without a console, all ones (INVALID_HANDLE_VALUE) on error.
+            if handle.is_null() || handle as isize == -1 {
+                return;
+            }
+            let len = u32::try_from(bytes.len()).unwrap_or(u32::MAX);
+            let mut written = 0;
+            unsafe {
+                WriteFile(
+                    handle,
+                    bytes.as_ptr(),
+                    len,
+                    &mut written,
+                    core::ptr::null_mut(),
+                );
+            }
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        let _ = bytes;
+    }
+
+    /// Fill `buf` from the OS cryptographic random number generator.
+    ///
+    /// Uses `getrandom` on Linux, Android and FreeBSD, `getentropy` on
+    /// Apple platforms and `RtlGenRandom` on Windows. Does not allocate.
+    /// Returns `false` if no generator is available or it fails.
+    pub fn fill_random(buf: &mut [u8]) -> bool {
+        #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+        {
+            let mut filled = 0;
+            while filled < buf.len() {
+                let rest = &mut buf[filled..];
+                let n = unsafe { libc::getrandom(rest.as_mut_ptr() as *mut _, rest.len(), 0) };
+                if n < 0 {
+                    if std::io::Error::last_os_error().raw_os_error() == Some(libc::EINTR) {
+                        continue;
+                    }
+                    return false;
+                }
+                filled += n as usize;
+            }
+            true
+        }
+
+        #[cfg(target_vendor = "apple")]
+        {
+            // getentropy is limited to 256 bytes per call.
+            buf.chunks_mut(256).all(
+                |chunk| unsafe { libc::getentropy(chunk.as_mut_ptr() as *mut _, chunk.len()) } == 0,
+            )
+        }
+
+        #[cfg(windows)]
+        {
+            #[link(name = "advapi32")]
+            extern "system" {
+                // Exported as `RtlGenRandom`'s real symbol name.
+                fn SystemFunction036(buffer: *mut u8, length: u32) -> u8;
+            }
+            buf.chunks_mut(u32::MAX as usize).all(
+                |chunk| unsafe { SystemFunction036(chunk.as_mut_ptr(), chunk.len() as u32) } != 0,
+            )
+        }
+
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "freebsd",
+            target_vendor = "apple",
+            windows
+        )))]
+        {
+            let _ = buf;
+            false
+        }
+    }
+
+    /// Query the size of a virtual memory page.
+    ///
+    /// This is 16KB on Apple silicon, where [`PAGE_ALIGN`](crate::config::PAGE_ALIGN)
+    /// (4096) is only a lower bound. Falls back to `PAGE_ALIGN` if the OS
+    /// does not report a size. The OS is asked once; later calls return the
+    /// cached value.
+    #[inline]
+    pub fn page_size() -> usize {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // 0 until the first query.
+        static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+        match PAGE_SIZE.load(Ordering::Relaxed) {
+            0 => {
+                let size = query_page_size();
+                PAGE_SIZE.store(size, Ordering::Relaxed);
+                size
+            }
+            size => size,
+        }
+    }
+
+    #[cold]
+    fn query_page_size() -> usize {
+        use crate::config::PAGE_ALIGN;
+
+        #[cfg(unix)]
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+
+        #[cfg(windows)]
+        let size = {
+            #[repr(C)]
+            struct SystemInfo {
+                processor_architecture: u16,
+                reserved: u16,
+                page_size: u32,
+                minimum_application_address: *mut core::ffi::c_void,
+                maximum_application_address: *mut core::ffi::c_void,
+                active_processor_mask: usize,
+                number_of_processors: u32,
+                processor_type: u32,
+                allocation_granularity: u32,
+                processor_level: u16,
+                processor_revision: u16,
+            }
+            extern "system" {
+                fn GetSystemInfo(info: *mut SystemInfo);
+            }
+            let mut info = core::mem::MaybeUninit::<SystemInfo>::uninit();
+            unsafe {
+                GetSystemInfo(info.as_mut_ptr());
+                info.assume_init().page_size
+            }
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        let size = WASM_PAGE as u64;
+
+        #[cfg(not(any(unix, windows, target_arch = "wasm32")))]
+        let size = 0u32;
+
+        match usize::try_from(size) {
+            Ok(size) if size.is_power_of_two() => size,
+            _ => PAGE_ALIGN,
+        }
+    }
+
+    /// Read the calling thread's last OS error code.
+    #[cfg(all(unix, not(target_os = "linux")))]
+    #[inline]
+    fn errno() -> i32 {
+        std::io::Error::last_os_error().raw_os_error().unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_page_size() {
+        let size = sys::page_size();
+        assert!(size.is_power_of_two());
+        assert!(size >= crate::config::PAGE_ALIGN);
+
+        // Mappings start on a page boundary.
+        let ptr = sys::alloc(size).expect("allocation should succeed");
+        assert_eq!(ptr as usize % size, 0);
+        sys::dealloc(ptr, size).expect("deallocation should succeed");
+    }
+
+    #[test]
+    fn test_alloc_at_hint() {
+        let size = 64 * 1024;
+        let hint = sys::alloc(size).expect("allocation should succeed");
+        // An occupied hint is never clobbered: the OS picks another address.
+        let other = sys::alloc_at(hint, size).expect("allocation should succeed");
+        assert_ne!(other, hint);
+        unsafe { other.write_bytes(0xAB, size) };
+        sys::dealloc(other, size).expect("deallocation should succeed");
+        sys::dealloc(hint, size).expect("deallocation should succeed");
+    }
+
     #[test]
     fn test_alloc_dealloc_roundtrip() {
         let size = 4096;
@@ -319,10 +1384,99 @@ mod tests {
         sys::dealloc(ptr, size).expect("deallocation should succeed");
     }
 
+    #[test]
+    fn test_lock_unlock_roundtrip() {
+        let size = 64 * 1024;
+        let ptr = sys::alloc(size).expect("allocation should succeed");
+
+        match unsafe { sys::lock(ptr, size) } {
+            Ok(()) => unsafe { sys::unlock(ptr, size) }.expect("unlock should succeed"),
+            // Locked-memory limits may be too small in constrained sandboxes
+            Err(err) => assert_eq!(err.requested_size, size),
+        }
+
+        sys::dealloc(ptr, size).expect("deallocation should succeed");
+    }
+
+    #[test]
+    fn test_huge_page_alloc_or_error() {
+        let size = 2 * 1024 * 1024;
+        // Succeeds only if 2MB huge pages are reserved on this machine
+        match sys::alloc_huge_pages(size, size) {
+            Ok(ptr) => {
+                unsafe { *ptr = 0x42 };
+                sys::dealloc(ptr, size).expect("deallocation should succeed");
+            }
+            Err(err) => assert_eq!(err.requested_size, size),
+        }
+    }
+
+    #[test]
+    fn test_protect_read_only() {
+        let size = 4096;
+        let ptr = sys::alloc(size).expect("allocation should succeed");
+
+        unsafe { *ptr = 0x42 };
+        unsafe { sys::protect(ptr, size, Protection::Read) }.expect("protect should succeed");
+        assert_eq!(unsafe { *ptr }, 0x42);
+        unsafe { sys::protect(ptr, size, Protection::ReadWrite) }.expect("protect should succeed");
+        unsafe { *ptr = 0x43 };
+
+        sys::dealloc(ptr, size).expect("deallocation should succeed");
+    }
+
+    #[test]
+    fn test_release_keeps_mapping_usable() {
+        let size = 16 * 4096;
+        let ptr = sys::alloc(size).expect("allocation should succeed");
+
+        unsafe { std::ptr::write_bytes(ptr, 0xAB, size) };
+        unsafe { sys::release(ptr, size) }.expect("release should succeed");
+
+        // Still mapped and writable
+        unsafe {
+            *ptr = 0x42;
+            assert_eq!(*ptr, 0x42);
+        }
+
+        sys::dealloc(ptr, size).expect("deallocation should succeed");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_alloc_failure_carries_errno() {
+        // Larger than any address space: mmap rejects it with ENOMEM.
+        let size = usize::MAX & !4095;
+        let err = sys::alloc(size).unwrap_err();
+        assert_eq!(err.requested_size, size);
+        assert_eq!(err.error_code, Some(libc::ENOMEM));
+    }
+
     #[test]
     fn test_alloc_failed_display() {
         let err = AllocFailed::new(1024);
         let msg = format!("{}", err);
         assert!(msg.contains("1024"));
     }
+
+    #[test]
+    fn test_fill_random() {
+        let mut a = [0u8; 32];
+        let mut b = [0u8; 32];
+        assert!(sys::fill_random(&mut a));
+        assert!(sys::fill_random(&mut b));
+        assert_ne!(a, b);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_alloc_failed_serde() {
+        let err = AllocFailed::with_code(4096, 12);
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(json, r#"{"requested_size":4096,"error_code":12}"#);
+
+        let back: AllocFailed = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.requested_size, 4096);
+        assert_eq!(back.error_code, Some(12));
+    }
 }
@@ -6,13 +6,24 @@
 //! - **4KB page alignment**: Optionally available for huge vector allocations.
 //! - **Massive capacity**: Pre-reserved for 1GB+ polynomial vectors.
 
+use crate::boxed::ArenaBox;
 use crate::bump::BumpAlloc;
-use crate::config::{CACHE_LINE_ALIGN, PAGE_ALIGN};
+use crate::config::{CACHE_COLOR_SPAN, CACHE_LINE_ALIGN};
+use crate::platform::{sys, AllocFailed};
+use crate::zeroable::Zeroable;
+use std::alloc::Layout;
+use std::ptr::NonNull;
 use std::sync::Arc;
 
 /// Specialized handle for Polynomial and FFT data.
 ///
 /// Optimized for cache-line alignment and massive vectors.
+///
+/// With the `nightly-allocator-api` feature, this handle implements
+/// [`core::alloc::Allocator`], so collections can be built directly on the
+/// arena (e.g. `Vec::new_in(alloc.polynomial())`). Such collections must not
+/// outlive a [`reset`](Self::reset) of the arena: their memory is reused.
+#[derive(Clone)]
 pub struct PolynomialArena {
     inner: Arc<BumpAlloc>,
 }
@@ -36,7 +47,15 @@ impl PolynomialArena {
         self.inner.alloc(size, CACHE_LINE_ALIGN)
     }
 
-    /// Allocate huge vectors with page alignment (4096 bytes).
+    /// Like [`alloc_fft_friendly`](Self::alloc_fft_friendly), but returns
+    /// `None` instead of a null pointer when the arena is exhausted.
+    #[inline]
+    pub fn alloc_fft_friendly_nn(&self, size: usize) -> Option<NonNull<u8>> {
+        NonNull::new(self.alloc_fft_friendly(size))
+    }
+
+    /// Allocate huge vectors aligned to the OS page size
+    /// ([`sys::page_size`], at least 4096 bytes).
     ///
     /// Use this for vectors exceeding a few megabytes. Benefits:
     /// - **TLB efficiency**: Reduces translation lookaside buffer misses
@@ -45,7 +64,7 @@ impl PolynomialArena {
     #[inline]
     pub fn alloc_huge(&self, size: usize) -> *mut u8 {
         debug_assert!(size > 0);
-        self.inner.alloc(size, PAGE_ALIGN)
+        self.inner.alloc(size, sys::page_size())
     }
 
     /// Allocate with custom alignment.
@@ -60,6 +79,143 @@ impl PolynomialArena {
         self.inner.alloc(size, align)
     }
 
+    /// Allocate memory for `layout`.
+    #[inline]
+    pub fn alloc_layout(&self, layout: Layout) -> *mut u8 {
+        self.alloc(layout.size(), layout.align())
+    }
+
+    /// Allocate zero-initialized memory with custom alignment.
+    ///
+    /// Fresh arena memory is already zero, so zeroing only happens after
+    /// the arena has been reset. See [`BumpAlloc::alloc_zeroed`].
+    #[inline]
+    pub fn alloc_zeroed(&self, size: usize, align: usize) -> *mut u8 {
+        debug_assert!(size > 0);
+        debug_assert!(align.is_power_of_two());
+        self.inner.alloc_zeroed(size, align)
+    }
+
+    /// Allocate a 64-byte aligned `total_len`-byte buffer whose bytes past
+    /// `used_len` are zero, e.g. an NTT input rounded up to a power of two.
+    ///
+    /// The first `used_len` bytes are left for the caller to fill. Fresh
+    /// arena memory is already zero, so the tail is only zeroed after the
+    /// arena has been reset. See [`BumpAlloc::alloc_zero_padded`].
+    #[inline]
+    pub fn alloc_zero_padded(&self, used_len: usize, total_len: usize) -> *mut u8 {
+        debug_assert!(total_len > 0);
+        self.inner
+            .alloc_zero_padded(used_len, total_len, CACHE_LINE_ALIGN)
+    }
+
+    /// Like [`alloc`](Self::alloc), but returns `None` instead of a null
+    /// pointer when the arena is exhausted.
+    #[inline]
+    pub fn alloc_nn(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        NonNull::new(self.alloc(size, align))
+    }
+
+    /// Allocate polynomial data on a specific cache-coloring stripe.
+    ///
+    /// A cache line at address `a` lands in set `(a / 64) % num_sets`, so the
+    /// set index repeats every `CACHE_COLOR_SPAN` (64 bytes * `num_sets`) bytes.
+    /// This splits that span into `num_stripes` equal, cache-line-aligned
+    /// stripes of `width = CACHE_COLOR_SPAN / num_stripes` bytes, and places the
+    /// allocation so that `addr % CACHE_COLOR_SPAN == stripe * width`.
+    ///
+    /// Buffers on different stripes therefore start in different cache sets,
+    /// which keeps blocked FFT passes over them from evicting each other.
+    /// Each call may pad up to `CACHE_COLOR_SPAN` bytes to reach its stripe.
+    ///
+    /// Returns a null pointer if `stripe` is not below `num_stripes`, or if
+    /// the span cannot be split into `num_stripes` stripes of at least one
+    /// cache line (including `num_stripes == 0`).
+    #[inline]
+    pub fn alloc_stripe(&self, size: usize, stripe: usize, num_stripes: usize) -> *mut u8 {
+        debug_assert!(size > 0);
+        if stripe >= num_stripes || num_stripes > CACHE_COLOR_SPAN / CACHE_LINE_ALIGN {
+            return std::ptr::null_mut();
+        }
+
+        let width = (CACHE_COLOR_SPAN / num_stripes) & !(CACHE_LINE_ALIGN - 1);
+        self.inner
+            .alloc_with_phase(size, CACHE_COLOR_SPAN, stripe * width)
+    }
+
+    /// Allocate polynomial data on the next cache color.
+    ///
+    /// Like [`alloc_stripe`](Self::alloc_stripe), but the stripe rotates on
+    /// its own: back-to-back large NTT vectors start
+    /// [`color_stride`](BumpAlloc::color_stride) bytes apart in cache-set
+    /// space. The padding this costs is reported as
+    /// [`ArenaStats::polynomial_color_waste_bytes`](crate::ArenaStats::polynomial_color_waste_bytes).
+    #[inline]
+    pub fn alloc_fft_friendly_colored(&self, size: usize) -> *mut u8 {
+        debug_assert!(size > 0);
+        self.inner.alloc_colored(size)
+    }
+
+    /// Set the coloring stride of the arena; see
+    /// [`BumpAlloc::set_color_stride`].
+    #[inline]
+    pub fn set_color_stride(&self, stride: usize) {
+        self.inner.set_color_stride(stride);
+    }
+
+    /// Allocate a zero-initialized slice of `len` elements.
+    ///
+    /// The safe counterpart of [`alloc_slice`](Self::alloc_slice): the
+    /// slice is aligned to `align_of::<T>()`, at least 64 bytes, and stays
+    /// valid until the arena is reset. Returns `None` if the arena is
+    /// exhausted or the size overflows.
+    #[inline]
+    #[allow(clippy::mut_from_ref)] // the slice is memory past the cursor, never `self`
+    pub fn alloc_slice_zeroed<T: Zeroable>(&self, len: usize) -> Option<&mut [T]> {
+        let size = len.checked_mul(std::mem::size_of::<T>())?;
+        if size == 0 {
+            return Some(&mut []);
+        }
+        let align = std::mem::align_of::<T>().max(CACHE_LINE_ALIGN);
+        let ptr = NonNull::new(self.inner.alloc_zeroed(size, align))?.as_ptr();
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr as *mut T, len) })
+    }
+
+    /// Allocate a zero-initialized slice of `len` elements aligned to
+    /// `ALIGN` bytes.
+    ///
+    /// Puts the SIMD alignment contract in the type, e.g.
+    /// `alloc_aligned::<Fr, 64>(n)` for AVX-512 loads. `ALIGN` must be a
+    /// power of two no smaller than `align_of::<T>()`; this is checked at
+    /// compile time. Returns `None` if the arena is exhausted or the size
+    /// overflows.
+    #[inline]
+    #[allow(clippy::mut_from_ref)] // no two calls get overlapping blocks before a reset
+    pub fn alloc_aligned<T: Zeroable, const ALIGN: usize>(&self, len: usize) -> Option<&mut [T]> {
+        const {
+            assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of two");
+            assert!(
+                ALIGN >= std::mem::align_of::<T>(),
+                "ALIGN must be at least the alignment of T"
+            );
+        }
+        let size = len.checked_mul(std::mem::size_of::<T>())?;
+        if size == 0 {
+            return Some(&mut []);
+        }
+        let ptr = NonNull::new(self.inner.alloc_zeroed(size, ALIGN))?.as_ptr();
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr as *mut T, len) })
+    }
+
+    /// Move a single value into the arena, e.g. a transcript or config
+    /// struct. Returns `None` if the arena is exhausted.
+    ///
+    /// The value is never dropped; see [`ArenaBox`].
+    #[inline]
+    pub fn boxed<T: Copy>(&self, value: T) -> Option<ArenaBox<'_, T>> {
+        ArenaBox::new_in(&self.inner, value)
+    }
+
     /// Allocate a typed slice of elements with appropriate alignment.
     ///
     /// This is a convenience method for allocating arrays of field elements
@@ -85,6 +241,30 @@ impl PolynomialArena {
         self.inner.reset();
     }
 
+    /// Make every polynomial allocated so far read-only.
+    ///
+    /// Use this once coefficients are computed and only read afterwards
+    /// (e.g. during commitment): any stray write then faults. Polynomials
+    /// allocated while frozen start on the next page and stay writable.
+    /// Call [`unfreeze`](Self::unfreeze) before the next reset. See
+    /// [`BumpAlloc::freeze`].
+    #[inline]
+    pub fn freeze(&self) -> Result<(), AllocFailed> {
+        self.inner.freeze()
+    }
+
+    /// Make the polynomial arena writable again after [`freeze`](Self::freeze).
+    #[inline]
+    pub fn unfreeze(&self) -> Result<(), AllocFailed> {
+        self.inner.unfreeze()
+    }
+
+    /// Check if the polynomial arena is frozen read-only.
+    #[inline]
+    pub fn is_frozen(&self) -> bool {
+        self.inner.is_frozen()
+    }
+
     /// Get the remaining capacity in bytes.
     #[inline]
     pub fn remaining(&self) -> usize {
@@ -102,6 +282,73 @@ impl PolynomialArena {
     pub fn capacity(&self) -> usize {
         self.inner.capacity()
     }
+
+    /// Get the `[base, limit)` addresses of the region; see
+    /// [`BumpAlloc::region`].
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        self.inner.region()
+    }
+}
+
+#[cfg(feature = "nightly-allocator-api")]
+unsafe impl core::alloc::Allocator for PolynomialArena {
+    fn allocate(
+        &self,
+        layout: core::alloc::Layout,
+    ) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        if layout.size() == 0 {
+            // Zero-sized allocations don't touch the arena.
+            let dangling = layout.align() as *mut u8;
+            let ptr = unsafe { NonNull::new_unchecked(dangling) };
+            return Ok(NonNull::slice_from_raw_parts(ptr, 0));
+        }
+
+        let ptr = self.alloc_nn(layout.size(), layout.align());
+        ptr.map(|p| NonNull::slice_from_raw_parts(p, layout.size()))
+            .ok_or(core::alloc::AllocError)
+    }
+
+    unsafe fn deallocate(&self, _ptr: NonNull<u8>, _layout: core::alloc::Layout) {
+        // Individual deallocation is a no-op in a bump allocator.
+        // Memory is reclaimed by calling reset() on the arena.
+    }
+
+    unsafe fn grow(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: core::alloc::Layout,
+        new_layout: core::alloc::Layout,
+    ) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        debug_assert!(new_layout.size() >= old_layout.size());
+
+        // Allocate a new block and copy the old data
+        let new_ptr = self.allocate(new_layout)?;
+        std::ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr() as *mut u8, old_layout.size());
+        Ok(new_ptr)
+    }
+
+    unsafe fn shrink(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: core::alloc::Layout,
+        new_layout: core::alloc::Layout,
+    ) -> Result<NonNull<[u8]>, core::alloc::AllocError> {
+        debug_assert!(new_layout.size() <= old_layout.size());
+
+        // The bump allocator doesn't shrink; keep the block in place unless
+        // the new layout demands a stricter alignment than it satisfies.
+        if !(ptr.as_ptr() as usize).is_multiple_of(new_layout.align()) {
+            let new_ptr = self.allocate(new_layout)?;
+            std::ptr::copy_nonoverlapping(
+                ptr.as_ptr(),
+                new_ptr.as_ptr() as *mut u8,
+                new_layout.size(),
+            );
+            return Ok(new_ptr);
+        }
+        Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()))
+    }
 }
 
 #[cfg(test)]
@@ -109,6 +356,79 @@ mod tests {
     use super::*;
     use crate::arena::ArenaManager;
 
+    #[test]
+    fn test_alloc_zeroed_after_reset() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let ptr = poly.alloc_zeroed(4096, 64);
+        assert!(unsafe { std::slice::from_raw_parts(ptr, 4096) }
+            .iter()
+            .all(|&b| b == 0));
+        unsafe {
+            std::ptr::write_bytes(ptr, 0xAB, 4096);
+            poly.reset();
+        }
+
+        let again = poly.alloc_zeroed(4096, 64);
+        assert_eq!(again, ptr);
+        assert!(unsafe { std::slice::from_raw_parts(again, 4096) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_alloc_zero_padded_after_reset() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let ptr = poly.alloc_fft_friendly(8192);
+        unsafe {
+            std::ptr::write_bytes(ptr, 0xAB, 8192);
+            poly.reset();
+        }
+
+        let padded = poly.alloc_zero_padded(3000, 8192);
+        assert_eq!(padded, ptr);
+        let data = unsafe { std::slice::from_raw_parts(padded, 8192) };
+        // The used prefix still holds old data, the padding is zero.
+        assert!(data[..3000].iter().all(|&b| b != 0));
+        assert!(data[3000..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_freeze_unfreeze() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let coeffs = poly.alloc_slice_zeroed::<u64>(1024).unwrap();
+        coeffs.fill(42);
+        // Ends partway into a page.
+        let tail = poly
+            .alloc_slice_zeroed::<u64>(3)
+            .unwrap()
+            .as_ptr_range()
+            .end;
+        poly.freeze().unwrap();
+        assert!(poly.is_frozen());
+        assert!(coeffs.iter().all(|&c| c == 42));
+
+        // Allocating while frozen lands past the frozen pages.
+        let more = poly.alloc_slice_zeroed::<u64>(8).unwrap();
+        more.fill(1);
+        assert!(more.as_ptr() as usize >= (tail as usize).next_multiple_of(sys::page_size()));
+
+        poly.unfreeze().unwrap();
+        assert!(!poly.is_frozen());
+        coeffs.fill(7);
+        unsafe { poly.reset() };
+
+        // Nothing allocated: freezing is a no-op.
+        poly.freeze().unwrap();
+        assert!(!poly.is_frozen());
+    }
+
     #[test]
     fn test_fft_alignment() {
         let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
@@ -134,13 +454,47 @@ mod tests {
             let ptr = poly.alloc_huge(64 * 1024);
             assert!(!ptr.is_null());
             assert_eq!(
-                (ptr as usize) % PAGE_ALIGN,
+                (ptr as usize) % sys::page_size(),
                 0,
                 "Huge allocation not page-aligned"
             );
         }
     }
 
+    #[test]
+    fn test_alloc_slice_zeroed() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let coeffs = poly.alloc_slice_zeroed::<u64>(1024).unwrap();
+        assert_eq!(coeffs.len(), 1024);
+        assert_eq!(coeffs.as_ptr() as usize % 64, 0);
+        coeffs.fill(7);
+
+        unsafe { poly.reset() };
+        let coeffs = poly.alloc_slice_zeroed::<[u64; 4]>(256).unwrap();
+        assert!(coeffs.iter().all(|e| *e == [0; 4]));
+
+        assert!(poly.alloc_slice_zeroed::<u64>(0).unwrap().is_empty());
+        assert!(poly.alloc_slice_zeroed::<u64>(usize::MAX).is_none());
+    }
+
+    #[test]
+    fn test_alloc_aligned() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        // Skew the cursor off every large boundary first.
+        poly.alloc(8, 8);
+        let elements = poly.alloc_aligned::<[u64; 4], 128>(16).unwrap();
+        assert_eq!(elements.len(), 16);
+        assert_eq!(elements.as_ptr() as usize % 128, 0);
+        assert!(elements.iter().all(|e| *e == [0; 4]));
+
+        assert!(poly.alloc_aligned::<u64, 64>(0).unwrap().is_empty());
+        assert!(poly.alloc_aligned::<u64, 64>(usize::MAX).is_none());
+    }
+
     #[test]
     fn test_typed_slice_allocation() {
         let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
@@ -184,4 +538,77 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_fft_friendly_nn() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let ptr = poly
+            .alloc_fft_friendly_nn(1024)
+            .expect("allocation should succeed");
+        assert_eq!((ptr.as_ptr() as usize) % CACHE_LINE_ALIGN, 0);
+
+        // Larger than the whole arena
+        assert!(poly.alloc_fft_friendly_nn(2 * 1024 * 1024).is_none());
+    }
+
+    #[test]
+    fn test_stripe_offsets() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let num_stripes = 8;
+        let width = CACHE_COLOR_SPAN / num_stripes;
+        for stripe in 0..num_stripes {
+            let ptr = poly.alloc_stripe(4096, stripe, num_stripes);
+            assert!(!ptr.is_null());
+            assert_eq!((ptr as usize) % CACHE_COLOR_SPAN, stripe * width);
+            assert_eq!((ptr as usize) % CACHE_LINE_ALIGN, 0);
+        }
+
+        let used = poly.used();
+        assert!(poly.alloc_stripe(4096, 0, 0).is_null());
+        assert!(poly.alloc_stripe(4096, 8, 8).is_null());
+        let too_many = CACHE_COLOR_SPAN / CACHE_LINE_ALIGN + 1;
+        assert!(poly.alloc_stripe(4096, 0, too_many).is_null());
+        assert_eq!(poly.used(), used);
+        assert!(!poly
+            .alloc_stripe(4096, too_many - 2, too_many - 1)
+            .is_null());
+    }
+
+    #[test]
+    fn test_colored_allocations_rotate() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+        poly.set_color_stride(16 * 1024);
+
+        // Equal power-of-two buffers would otherwise share every low bit.
+        for color in [0, 1, 2, 3, 0] {
+            let ptr = poly.alloc_fft_friendly_colored(128 * 1024);
+            assert!(!ptr.is_null());
+            assert_eq!((ptr as usize) % CACHE_COLOR_SPAN, color * 16 * 1024);
+        }
+        assert_eq!(manager.polynomial().next_color(), 1);
+
+        let stats = manager.stats();
+        assert!(stats.polynomial_color_waste_bytes > 0);
+        assert!(stats.polynomial_color_waste_bytes <= stats.alignment_waste_bytes);
+    }
+
+    #[cfg(feature = "nightly-allocator-api")]
+    #[test]
+    fn test_vec_new_in() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 2 * 1024 * 1024, 1024 * 1024).unwrap();
+        let poly = PolynomialArena::new(manager.polynomial());
+
+        let mut coeffs: Vec<u64, _> = Vec::with_capacity_in(16, poly.clone());
+        assert_eq!((coeffs.as_ptr() as usize) % 8, 0);
+        for i in 0..1024 {
+            coeffs.push(i);
+        }
+        assert!(poly.used() >= 1024 * 8);
+        assert!(coeffs.iter().copied().eq(0..1024));
+    }
 }
@@ -0,0 +1,123 @@
+//! Allocation replay for fuzzing.
+//!
+//! [`BumpAlloc::replay`] runs a recorded sequence of [`AllocOp`]s against an
+//! arena and returns what each one handed out, so a fuzz target can check
+//! the allocator's invariants:
+//!
+//! - **No overlap**: Blocks live at the same time never share a byte.
+//! - **Alignment**: Every block honors the alignment it was requested with.
+//! - **Monotonic**: Blocks only move back toward the base after a reset.
+//!
+//! Requires the `fuzzing` feature.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use crate::bump::BumpAlloc;
+
+/// One step of an allocation sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllocOp {
+    /// [`BumpAlloc::alloc`] with the given size and alignment.
+    Alloc {
+        /// Requested size in bytes.
+        size: usize,
+        /// Requested alignment.
+        align: usize,
+    },
+    /// [`BumpAlloc::reset`].
+    Reset,
+    /// [`BumpAlloc::secure_reset`].
+    SecureReset,
+}
+
+impl BumpAlloc {
+    /// Apply `ops` in order and return the result of each one.
+    ///
+    /// Entry `i` is the pointer returned by `ops[i]`: null for a failed
+    /// allocation and for the resets. Requests `alloc` would reject in
+    /// debug builds (a zero size, or an alignment that is not a power of
+    /// two) fail with a null pointer instead, so arbitrary fuzz input is
+    /// safe to replay.
+    ///
+    /// # Safety
+    /// Memory allocated from the arena before a reset in `ops` (including
+    /// before this call) becomes invalid at that reset.
+    pub unsafe fn replay(&self, ops: &[AllocOp]) -> Vec<*mut u8> {
+        ops.iter()
+            .map(|&op| match op {
+                AllocOp::Alloc { size, align } if size > 0 && align.is_power_of_two() => {
+                    self.alloc(size, align)
+                }
+                AllocOp::Alloc { .. } => core::ptr::null_mut(),
+                AllocOp::Reset => {
+                    self.reset();
+                    core::ptr::null_mut()
+                }
+                AllocOp::SecureReset => {
+                    self.secure_reset();
+                    core::ptr::null_mut()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_invariants() {
+        let mut buffer = vec![0u8; 4096];
+        let alloc = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let ops = [
+            AllocOp::Alloc { size: 3, align: 1 },
+            AllocOp::Alloc {
+                size: 100,
+                align: 64,
+            },
+            AllocOp::Alloc { size: 0, align: 8 },
+            AllocOp::Alloc { size: 8, align: 3 },
+            AllocOp::Alloc {
+                size: usize::MAX,
+                align: 8,
+            },
+            AllocOp::Alloc {
+                size: 16,
+                align: 16,
+            },
+            AllocOp::Reset,
+            AllocOp::Alloc { size: 8, align: 8 },
+            AllocOp::SecureReset,
+            AllocOp::Alloc { size: 8, align: 8 },
+        ];
+        let ptrs = unsafe { alloc.replay(&ops) };
+        assert_eq!(ptrs.len(), ops.len());
+
+        let mut live: Vec<(usize, usize)> = Vec::new();
+        for (op, &ptr) in ops.iter().zip(&ptrs) {
+            match *op {
+                AllocOp::Alloc { size, align } if !ptr.is_null() => {
+                    let start = ptr as usize;
+                    assert_eq!(start % align, 0);
+                    assert!(live.iter().all(|&(s, e)| start >= e || start + size <= s));
+                    // Blocks ascend between resets.
+                    assert!(live.last().is_none_or(|&(_, e)| start >= e));
+                    live.push((start, start + size));
+                }
+                AllocOp::Alloc { .. } => {}
+                AllocOp::Reset | AllocOp::SecureReset => {
+                    assert!(ptr.is_null());
+                    live.clear();
+                }
+            }
+        }
+        assert!(ptrs[2].is_null() && ptrs[3].is_null() && ptrs[4].is_null());
+        assert_eq!(ptrs[7], ptrs[9]);
+        assert_eq!(
+            alloc.used(),
+            ptrs[9] as usize + 8 - alloc.base_ptr() as usize
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! Fixed-size arena with its storage baked in.
+//!
+//! [`StaticArena`] holds an `N`-byte buffer inline, so a `static` of it
+//! lands in `.bss` and needs no `mmap`, heap or caller-provided buffer:
+//! a fully static bump allocator for firmware and enclaves.
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::bump::BumpAlloc;
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const READY: u8 = 2;
+
+/// Cache-line aligned inline storage.
+#[repr(C, align(64))]
+struct Storage<const N: usize>([u8; N]);
+
+/// A bump arena over an inline `[u8; N]`, for use in a `static`.
+///
+/// The [`BumpAlloc`] over the buffer is set up on the first call to
+/// [`arena`](Self::arena), which therefore needs a `'static` reference:
+/// the buffer must never move once allocations point into it.
+///
+/// ```rust
+/// use zk_nalloc::StaticArena;
+///
+/// static SCRATCH: StaticArena<4096> = StaticArena::new();
+///
+/// let ptr = SCRATCH.arena().alloc(64, 8);
+/// assert!(!ptr.is_null());
+/// assert_eq!(SCRATCH.arena().capacity(), 4096);
+/// ```
+pub struct StaticArena<const N: usize> {
+    storage: UnsafeCell<Storage<N>>,
+    inner: UnsafeCell<MaybeUninit<BumpAlloc>>,
+    state: AtomicU8,
+}
+
+impl<const N: usize> StaticArena<N> {
+    /// Size of the arena in bytes.
+    pub const CAPACITY: usize = N;
+
+    /// Create the arena. `N` must be non-zero; this is checked at compile
+    /// time.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "StaticArena size must not be zero") };
+        Self {
+            storage: UnsafeCell::new(Storage([0; N])),
+            inner: UnsafeCell::new(MaybeUninit::uninit()),
+            state: AtomicU8::new(UNINIT),
+        }
+    }
+
+    /// Get the bump allocator over the inline buffer.
+    ///
+    /// `reset`, `secure_reset` and the statistics all work as on any other
+    /// [`BumpAlloc`].
+    #[inline]
+    pub fn arena(&'static self) -> &'static BumpAlloc {
+        if self.state.load(Ordering::Acquire) != READY {
+            self.init();
+        }
+        unsafe { (*self.inner.get()).assume_init_ref() }
+    }
+
+    #[cold]
+    fn init(&'static self) {
+        match self.state.compare_exchange(
+            UNINIT,
+            INITIALIZING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                // Safety: the buffer is zero-initialized and, being borrowed
+                // for `'static`, never moves; only this thread writes `inner`.
+                unsafe {
+                    let base = (*self.storage.get()).0.as_mut_ptr();
+                    (*self.inner.get()).write(BumpAlloc::new_zeroed(base, N));
+                }
+                self.state.store(READY, Ordering::Release);
+            }
+            Err(_) => {
+                while self.state.load(Ordering::Acquire) != READY {
+                    core::hint::spin_loop();
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for StaticArena<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Safety: the buffer is only accessed through the `BumpAlloc`, which is
+// `Sync`, and `inner` is written once before `state` publishes it.
+unsafe impl<const N: usize> Sync for StaticArena<N> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_static_arena() {
+        static ARENA: StaticArena<1024> = StaticArena::new();
+        let arena = ARENA.arena();
+        assert!(core::ptr::eq(arena, ARENA.arena()));
+        assert_eq!(arena.capacity(), StaticArena::<1024>::CAPACITY);
+        assert_eq!(arena.base_ptr() as usize % 64, 0);
+
+        let ptr = arena.alloc_zeroed(512, 8);
+        assert!(!ptr.is_null());
+        unsafe { ptr.write_bytes(0xEE, 512) };
+        assert!(arena.alloc(1024, 8).is_null());
+        assert_eq!(arena.alloc_fail_count(), 1);
+
+        unsafe { arena.secure_reset() };
+        assert_eq!(arena.used(), 0);
+        assert!(unsafe { core::slice::from_raw_parts(ptr, 512) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,186 @@
+//! Transcript Arena for nalloc.
+//!
+//! The `TranscriptArena` builds a Fiat-Shamir transcript in place:
+//!
+//! - **Contiguous**: Appended messages follow each other with no padding.
+//! - **Zero-copy**: The finished transcript is one slice, ready for a hasher.
+//! - **Bounded**: Backed by a fixed region, so appends never reallocate.
+
+use crate::bump::{BumpAlloc, GrowDirection, Marker};
+use std::sync::Arc;
+
+/// Append-only byte buffer for proof transcripts.
+///
+/// Every byte allocated from the underlying arena after the handle was
+/// created belongs to the transcript, so nothing else may allocate from
+/// that arena while the transcript grows. Use
+/// [`NAlloc::transcript`](crate::NAlloc::transcript) to get a handle over a
+/// private region, or [`new`](Self::new) over a dedicated `BumpAlloc`.
+///
+/// The arena must grow [up](GrowDirection::Up) and must not be growable:
+/// appends have to land at rising, adjacent addresses. For the same
+/// reason [`append`](Self::append) takes `&mut self`, so one thread
+/// appends at a time.
+pub struct TranscriptArena {
+    inner: Arc<BumpAlloc>,
+    /// Cursor position where the transcript begins.
+    start: Marker,
+    start_addr: usize,
+    /// Address just past the last appended byte.
+    end: usize,
+}
+
+impl TranscriptArena {
+    /// Create a `TranscriptArena` starting at the current cursor of `inner`.
+    #[inline]
+    pub fn new(inner: Arc<BumpAlloc>) -> Self {
+        debug_assert_eq!(
+            inner.direction(),
+            GrowDirection::Up,
+            "transcript arena must grow up"
+        );
+        #[cfg(feature = "growable")]
+        debug_assert!(
+            !inner.is_growable(),
+            "transcript arena must not be growable"
+        );
+        let start = inner.checkpoint();
+        let start_addr = inner.base_ptr() as usize + inner.used();
+        Self {
+            inner,
+            start,
+            start_addr,
+            end: start_addr,
+        }
+    }
+
+    /// Append `bytes` right after the previous message.
+    ///
+    /// Returns a pointer to the copy, or a null pointer if the transcript is
+    /// full. An empty message appends nothing and returns the current end.
+    /// A message the arena places anywhere but right after the previous one
+    /// is not appended either; see the type-level docs.
+    #[inline]
+    pub fn append(&mut self, bytes: &[u8]) -> *mut u8 {
+        let end = self.end;
+        if bytes.is_empty() {
+            return end as *mut u8;
+        }
+        let ptr = self.inner.alloc(bytes.len(), 1);
+        if ptr.is_null() {
+            return ptr;
+        }
+        if ptr as usize != end {
+            debug_assert!(false, "transcript arena shared with another allocator");
+            return std::ptr::null_mut();
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len()) };
+        self.end = end + bytes.len();
+        ptr
+    }
+
+    /// Get everything appended so far as one contiguous slice.
+    #[inline]
+    pub fn finalize(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.start_addr as *const u8, self.len()) }
+    }
+
+    /// Get the transcript length in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end - self.start_addr
+    }
+
+    /// Check if nothing has been appended yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of bytes that can still be appended.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.inner.remaining()
+    }
+
+    /// Discard the transcript and start a new one in the same space.
+    ///
+    /// # Safety
+    /// Pointers returned by [`append`](Self::append) become invalid.
+    #[inline]
+    pub unsafe fn reset(&mut self) {
+        let _ = self.inner.restore(self.start);
+        self.end = self.start_addr;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NAlloc;
+
+    #[test]
+    fn test_append_is_contiguous() {
+        let alloc = NAlloc::new();
+        let mut transcript = alloc.transcript(1024).unwrap();
+        assert!(transcript.is_empty());
+
+        let a = transcript.append(b"domain");
+        let b = transcript.append(&[1, 2, 3]);
+        assert_eq!(b as usize, a as usize + 6);
+        assert_eq!(transcript.append(&[]), unsafe { b.add(3) });
+        assert_eq!(transcript.finalize(), b"domain\x01\x02\x03");
+
+        assert!(transcript.append(&[0; 2048]).is_null());
+        assert_eq!(transcript.len(), 9);
+        assert_eq!(transcript.remaining(), 1024 - 9);
+
+        unsafe { transcript.reset() };
+        assert!(transcript.finalize().is_empty());
+        assert_eq!(transcript.append(b"x"), a);
+    }
+
+    #[test]
+    fn test_starts_at_current_cursor() {
+        let mut buffer = vec![0u8; 256];
+        let arena = Arc::new(unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) });
+        let header = arena.alloc(16, 1);
+
+        let mut transcript = TranscriptArena::new(arena.clone());
+        let first = transcript.append(b"abc");
+        assert_eq!(first, unsafe { header.add(16) });
+        assert_eq!(transcript.finalize(), b"abc");
+
+        // Reset only discards the transcript.
+        unsafe { transcript.reset() };
+        assert_eq!(arena.used(), 16);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "shared with another allocator")
+    )]
+    fn test_rejects_interleaved_allocations() {
+        let mut buffer = vec![0u8; 256];
+        let arena = Arc::new(unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) });
+        let mut transcript = TranscriptArena::new(arena.clone());
+
+        assert!(!transcript.append(b"abc").is_null());
+        let _ = arena.alloc(8, 1);
+        assert!(transcript.append(b"def").is_null());
+        assert_eq!(transcript.finalize(), b"abc");
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "must grow up"))]
+    fn test_rejects_downward_arena() {
+        let mut buffer = vec![0u8; 256];
+        let arena = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) }
+            .with_direction(GrowDirection::Down);
+        let mut transcript = TranscriptArena::new(Arc::new(arena));
+
+        assert!(transcript.append(b"abc").is_null());
+        assert!(transcript.finalize().is_empty());
+    }
+}
@@ -5,22 +5,168 @@
 //!
 //! - **Conditional zero on allocation**: Only zeroes recycled memory.
 //! - **Secure wipe on reset**: Zeroes all memory before recycling using volatile writes.
+//! - **Page locking**: Optionally keeps witness pages out of swap.
 
+use crate::boxed::ArenaBox;
 use crate::bump::BumpAlloc;
+use crate::config::{SECURE_WIPE_PATTERN, WITNESS_MIN_ALIGN};
+use crate::platform::AllocFailed;
+use crate::zeroable::Zeroable;
+use std::alloc::Layout;
+use std::fmt;
+use std::ptr::NonNull;
+use std::sync::atomic::{compiler_fence, Ordering};
 use std::sync::Arc;
 
+/// How [`WitnessArena::secure_wipe`] overwrites witness memory.
+///
+/// The region is overwritten `passes` times; pass `i` uses
+/// `patterns[i % patterns.len()]`. The default is a single zero pass.
+///
+/// ```rust
+/// use zk_nalloc::{NAlloc, WipePolicy};
+///
+/// let alloc = NAlloc::new();
+/// let witness = alloc.witness().with_wipe_policy(WipePolicy {
+///     passes: 3,
+///     patterns: &[0x00, 0xFF, 0xAA],
+/// });
+/// # let _ = witness;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WipePolicy {
+    /// Number of overwrite passes.
+    pub passes: usize,
+    /// Fill patterns, cycled through one per pass.
+    pub patterns: &'static [u8],
+}
+
+impl Default for WipePolicy {
+    fn default() -> Self {
+        Self {
+            passes: 1,
+            patterns: &[SECURE_WIPE_PATTERN],
+        }
+    }
+}
+
+/// Error returned by [`WitnessArena::secure_wipe_verified`] when wiped
+/// memory did not read back as the wipe pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WipeError {
+    /// Offset of the first surviving byte from the arena base.
+    pub offset: usize,
+}
+
+impl std::error::Error for WipeError {}
+
+impl fmt::Display for WipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Secure wipe verification failed: byte at offset {} survived",
+            self.offset
+        )
+    }
+}
+
+/// A background wipe started by [`WitnessArena::secure_wipe_async`].
+///
+/// Joins the wiper thread on drop.
+#[cfg(feature = "async-wipe")]
+#[must_use = "the arena must not be reused until the wipe is joined"]
+pub struct WipeHandle {
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(feature = "async-wipe")]
+impl WipeHandle {
+    /// Block until the wipe has finished and the arena is reset.
+    pub fn join(mut self) {
+        self.wait();
+    }
+
+    /// Check whether the wipe has finished, without blocking.
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|t| t.is_finished())
+    }
+
+    fn wait(&mut self) {
+        if let Some(thread) = self.thread.take() {
+            // `secure_wipe` does not panic; if it somehow did, the arena
+            // stays un-reset, which is the safe outcome.
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(feature = "async-wipe")]
+impl Drop for WipeHandle {
+    fn drop(&mut self) {
+        self.wait();
+    }
+}
+
 /// Specialized handle for Witness memory.
 ///
 /// Ensures zeroing on allocation (for recycled memory) and secure wiping on reset.
+///
+/// In debug builds every allocation made through this handle is tainted:
+/// a plain [`BumpAlloc::reset`] or [`BumpAlloc::restore`] over it, or
+/// dropping the [`ArenaManager`](crate::ArenaManager) that owns it, panics
+/// with the offset of the first region that was never securely wiped.
 pub struct WitnessArena {
     inner: Arc<BumpAlloc>,
+    policy: WipePolicy,
+    /// Alignment every allocation gets at least.
+    min_align: usize,
 }
 
 impl WitnessArena {
     /// Create a new `WitnessArena` wrapping a `BumpAlloc`.
     #[inline]
     pub fn new(inner: Arc<BumpAlloc>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            policy: WipePolicy::default(),
+            min_align: WITNESS_MIN_ALIGN,
+        }
+    }
+
+    /// Use `policy` for subsequent [`secure_wipe`](Self::secure_wipe) calls.
+    #[inline]
+    pub fn with_wipe_policy(mut self, policy: WipePolicy) -> Self {
+        debug_assert!(policy.passes > 0);
+        debug_assert!(!policy.patterns.is_empty());
+        self.policy = policy;
+        self
+    }
+
+    /// Align every subsequent allocation to at least `align` bytes, a power
+    /// of two, whatever the caller requests.
+    ///
+    /// Defaults to [`WITNESS_MIN_ALIGN`] (one cache line), so a secret no
+    /// larger than a line never straddles two: cache residency of secrets
+    /// is a timing side channel. Pass 1 to honour the requested alignment
+    /// only.
+    #[inline]
+    pub fn with_min_align(mut self, align: usize) -> Self {
+        debug_assert!(align.is_power_of_two());
+        self.min_align = align;
+        self
+    }
+
+    /// Get the minimum alignment set with
+    /// [`with_min_align`](Self::with_min_align).
+    #[inline]
+    pub fn min_align(&self) -> usize {
+        self.min_align
+    }
+
+    /// Get the wipe policy used by [`secure_wipe`](Self::secure_wipe).
+    #[inline]
+    pub fn wipe_policy(&self) -> WipePolicy {
+        self.policy
     }
 
     /// Allocate witness data.
@@ -30,25 +176,88 @@ impl WitnessArena {
     /// - Recycled memory (after `secure_wipe`) is explicitly zeroed here.
     ///
     /// This optimization avoids redundant zeroing on first use while
-    /// maintaining security guarantees for recycled memory.
+    /// maintaining security guarantees for recycled memory. An arena not
+    /// created with [`BumpAlloc::new_zeroed`] is always zeroed.
+    ///
+    /// The block is aligned to at least [`min_align`](Self::min_align).
     #[inline]
     pub fn alloc(&self, size: usize, align: usize) -> *mut u8 {
         debug_assert!(size > 0);
         debug_assert!(align > 0);
 
-        let ptr = self.inner.alloc(size, align);
+        let align = align.max(self.min_align);
+        let ptr = self.inner.alloc_zeroed(size, align);
+        #[cfg(debug_assertions)]
         if !ptr.is_null() {
-            // Only zero if this memory has been recycled.
-            // Fresh mmap'd memory is already zero (OS guarantee on Linux/macOS/Windows).
-            if self.inner.is_recycled() {
-                unsafe {
-                    std::ptr::write_bytes(ptr, 0, size);
-                }
-            }
+            self.inner.taint(ptr, size, align);
         }
         ptr
     }
 
+    /// Allocate zero-initialized memory for `layout`.
+    #[inline]
+    pub fn alloc_layout(&self, layout: Layout) -> *mut u8 {
+        self.alloc(layout.size(), layout.align())
+    }
+
+    /// Like [`alloc`](Self::alloc), but returns `None` instead of a null
+    /// pointer when the arena is exhausted.
+    #[inline]
+    pub fn alloc_nn(&self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        NonNull::new(self.alloc(size, align))
+    }
+
+    /// Allocate a zero-initialized slice of `len` witness elements.
+    ///
+    /// Suitable for field-element witness vectors. Returns `None` if the
+    /// arena is exhausted or the size overflows.
+    #[inline]
+    #[allow(clippy::mut_from_ref)] // the caller owns the block until the next wipe
+    pub fn alloc_slice_zeroed<T: Zeroable>(&self, len: usize) -> Option<&mut [T]> {
+        let size = len.checked_mul(std::mem::size_of::<T>())?;
+        if size == 0 {
+            return Some(&mut []);
+        }
+        let ptr = self.alloc_nn(size, std::mem::align_of::<T>())?.as_ptr();
+        Some(unsafe { std::slice::from_raw_parts_mut(ptr as *mut T, len) })
+    }
+
+    /// Move a single secret value into the witness arena.
+    ///
+    /// The value is never dropped, but it is wiped along with the rest of
+    /// the arena by [`secure_wipe`](Self::secure_wipe). Returns `None` if
+    /// the arena is exhausted.
+    #[inline]
+    pub fn boxed<T: Copy>(&self, value: T) -> Option<ArenaBox<'_, T>> {
+        let align = std::mem::align_of::<T>().max(self.min_align);
+        let value = ArenaBox::new_in_aligned(&self.inner, value, align)?;
+        #[cfg(debug_assertions)]
+        if std::mem::size_of::<T>() > 0 {
+            self.inner.taint(
+                ArenaBox::as_ptr(&value) as *mut u8,
+                std::mem::size_of::<T>(),
+                align,
+            );
+        }
+        Some(value)
+    }
+
+    /// Zero and fault in the first `bytes` of the arena during warmup.
+    ///
+    /// Call right after a reset, before the next proving round, with the
+    /// amount that round will allocate: its allocations then neither page
+    /// fault nor pay for zeroing recycled memory. See
+    /// [`BumpAlloc::prezero`].
+    ///
+    /// # Safety
+    /// The arena must be empty (just reset), and no allocation may run
+    /// concurrently with this call.
+    #[inline]
+    pub unsafe fn reserve(&self, bytes: usize) {
+        debug_assert_eq!(self.inner.used(), 0, "reserve called on a non-empty arena");
+        self.inner.prezero(bytes);
+    }
+
     /// Allocate witness data with explicit zero guarantee.
     ///
     /// Use this when you need a hard guarantee of zero-initialization,
@@ -58,11 +267,14 @@ impl WitnessArena {
         debug_assert!(size > 0);
         debug_assert!(align > 0);
 
+        let align = align.max(self.min_align);
         let ptr = self.inner.alloc(size, align);
         if !ptr.is_null() {
             unsafe {
                 std::ptr::write_bytes(ptr, 0, size);
             }
+            #[cfg(debug_assertions)]
+            self.inner.taint(ptr, size, align);
         }
         ptr
     }
@@ -70,13 +282,158 @@ impl WitnessArena {
     /// Securely wipe all witness data and reset the arena.
     ///
     /// Uses platform-specific secure zeroing (volatile writes) to ensure
-    /// the data is actually erased and cannot be recovered.
+    /// the data is actually erased and cannot be recovered. Each pass of
+    /// the [`WipePolicy`] is separated by a compiler fence.
     ///
     /// # Safety
     /// All previously allocated witness memory becomes invalid.
     #[inline]
     pub unsafe fn secure_wipe(&self) {
-        self.inner.secure_reset();
+        let patterns = match self.policy.patterns {
+            [] => &[SECURE_WIPE_PATTERN][..],
+            patterns => patterns,
+        };
+        for pass in 0..self.policy.passes.max(1) {
+            let pattern = patterns[pass % patterns.len()];
+            self.inner
+                .for_each_wipe_region(|ptr, len| BumpAlloc::volatile_memset(ptr, pattern, len));
+            compiler_fence(Ordering::SeqCst);
+        }
+        self.inner.finish_secure_reset();
+    }
+
+    /// Start [`secure_wipe`](Self::secure_wipe) on a background thread.
+    ///
+    /// The arena is reset only once the wipe completes, so wiped memory is
+    /// never handed out before it has been overwritten. Wait for that with
+    /// [`WipeHandle::join`]; dropping the handle joins as well.
+    ///
+    /// # Safety
+    /// All previously allocated witness memory becomes invalid. The witness
+    /// arena must not be allocated from, and its `ArenaManager` must not be
+    /// dropped, until the returned handle is joined or dropped.
+    #[cfg(feature = "async-wipe")]
+    pub unsafe fn secure_wipe_async(&self) -> WipeHandle {
+        let arena = WitnessArena::new(self.inner.clone())
+            .with_wipe_policy(self.policy)
+            .with_min_align(self.min_align);
+        let spawned = std::thread::Builder::new()
+            .name("nalloc-wiper".into())
+            .spawn(move || unsafe { arena.secure_wipe() });
+        match spawned {
+            Ok(thread) => WipeHandle {
+                thread: Some(thread),
+            },
+            Err(_) => {
+                // No thread available: wipe synchronously instead.
+                self.secure_wipe();
+                WipeHandle { thread: None }
+            }
+        }
+    }
+
+    /// Securely wipe the arena, then read every wiped byte back to confirm it.
+    ///
+    /// Catches wipes that were elided or did not reach memory. On failure
+    /// the arena is left un-reset, so the wipe can be retried, and the error
+    /// carries the offset of the first byte that survived. Costs roughly
+    /// twice a plain [`secure_wipe`](Self::secure_wipe).
+    ///
+    /// # Safety
+    /// All previously allocated witness memory becomes invalid.
+    pub unsafe fn secure_wipe_verified(&self) -> Result<(), WipeError> {
+        self.inner.for_each_wipe_region(|ptr, len| {
+            BumpAlloc::volatile_memset(ptr, SECURE_WIPE_PATTERN, len)
+        });
+        compiler_fence(Ordering::SeqCst);
+
+        let mut offset = 0;
+        let mut survivor = None;
+        self.inner.for_each_wipe_region(|ptr, len| {
+            if survivor.is_some() {
+                return;
+            }
+            survivor = (0..len)
+                .find(|&i| std::ptr::read_volatile(ptr.add(i)) != SECURE_WIPE_PATTERN)
+                .map(|i| offset + i);
+            offset += len;
+        });
+        if let Some(offset) = survivor {
+            return Err(WipeError { offset });
+        }
+
+        self.inner.finish_secure_reset();
+        Ok(())
+    }
+
+    /// Wipe with `passes` alternating 0x00/0xFF passes, then a random pass,
+    /// then a final zero pass, and reset the arena.
+    ///
+    /// Every pass uses volatile writes followed by a compiler fence. The
+    /// random pass uses a xorshift generator seeded from the arena address;
+    /// use [`secure_wipe_multipass_with`](Self::secure_wipe_multipass_with)
+    /// to supply your own randomness.
+    ///
+    /// # Safety
+    /// All previously allocated witness memory becomes invalid.
+    pub unsafe fn secure_wipe_multipass(&self, passes: usize) {
+        let mut state = (self.inner.base_ptr() as u64) | 1;
+        self.secure_wipe_multipass_with(passes, || {
+            // xorshift64: not cryptographic, only needs to defeat patterns.
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        });
+    }
+
+    /// Like [`secure_wipe_multipass`](Self::secure_wipe_multipass), but the
+    /// random pass draws its bytes from `rng`.
+    ///
+    /// # Safety
+    /// All previously allocated witness memory becomes invalid.
+    pub unsafe fn secure_wipe_multipass_with(&self, passes: usize, mut rng: impl FnMut() -> u64) {
+        for pass in 0..passes {
+            let pattern = if pass % 2 == 0 { 0x00 } else { 0xFF };
+            self.inner
+                .for_each_wipe_region(|ptr, len| BumpAlloc::volatile_memset(ptr, pattern, len));
+            compiler_fence(Ordering::SeqCst);
+        }
+
+        self.inner
+            .for_each_wipe_region(|ptr, len| BumpAlloc::volatile_fill_random(ptr, len, &mut rng));
+        compiler_fence(Ordering::SeqCst);
+
+        self.inner
+            .for_each_wipe_region(|ptr, len| BumpAlloc::volatile_memset(ptr, 0x00, len));
+        compiler_fence(Ordering::SeqCst);
+
+        self.inner.finish_secure_reset();
+    }
+
+    /// Lock the witness region into physical memory.
+    ///
+    /// Without this, the OS may page witness memory out to swap, where it can
+    /// persist after `secure_wipe`. The region stays locked until the owning
+    /// `ArenaManager` is dropped or [`unlock_pages`](Self::unlock_pages) is called.
+    ///
+    /// Fails (rather than panicking) if the locked-memory limit is exceeded,
+    /// e.g. `RLIMIT_MEMLOCK` on Linux.
+    #[inline]
+    pub fn lock_pages(&self) -> Result<(), AllocFailed> {
+        self.inner.lock_pages()
+    }
+
+    /// Unlock the witness region, allowing it to be swapped again.
+    #[inline]
+    pub fn unlock_pages(&self) -> Result<(), AllocFailed> {
+        self.inner.unlock_pages()
+    }
+
+    /// Check if the witness region is locked into physical memory.
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        self.inner.is_locked()
     }
 
     /// Get the remaining capacity in bytes.
@@ -97,6 +454,13 @@ impl WitnessArena {
         self.inner.capacity()
     }
 
+    /// Get the `[base, limit)` addresses of the region; see
+    /// [`BumpAlloc::region`].
+    #[inline]
+    pub fn region(&self) -> (usize, usize) {
+        self.inner.region()
+    }
+
     /// Check if the arena has been recycled (wiped and reset).
     #[inline]
     pub fn is_recycled(&self) -> bool {
@@ -109,6 +473,277 @@ mod tests {
     use super::*;
     use crate::arena::ArenaManager;
 
+    #[cfg(feature = "async-wipe")]
+    #[test]
+    fn test_secure_wipe_async() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let ptr = witness.alloc(4096, 8);
+        unsafe { std::ptr::write_bytes(ptr, 0xAA, 4096) };
+
+        let handle = unsafe { witness.secure_wipe_async() };
+        handle.join();
+        assert_eq!(witness.remaining(), 1024 * 1024);
+        let data = unsafe { std::slice::from_raw_parts(ptr, 4096) };
+        assert!(data.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_min_align() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+        assert_eq!(witness.min_align(), WITNESS_MIN_ALIGN);
+
+        // Byte-aligned requests still start on a cache line.
+        for _ in 0..4 {
+            assert_eq!(witness.alloc(3, 1) as usize % WITNESS_MIN_ALIGN, 0);
+        }
+        assert_eq!(witness.alloc_zeroed(5, 1) as usize % WITNESS_MIN_ALIGN, 0);
+        let secret = witness.boxed(7u8).unwrap();
+        assert_eq!(ArenaBox::as_ptr(&secret) as usize % WITNESS_MIN_ALIGN, 0);
+        // Stricter requests are honoured.
+        assert_eq!(witness.alloc(8, 4096) as usize % 4096, 0);
+
+        let packed = WitnessArena::new(manager.witness()).with_min_align(1);
+        let a = packed.alloc(3, 1);
+        assert_eq!(packed.alloc(3, 1), unsafe { a.add(3) });
+        unsafe { witness.secure_wipe() };
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_boxed_value_is_wiped() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let secret = witness.boxed([0x1234_5678_u64; 4]).unwrap();
+        assert_eq!(secret[3], 0x1234_5678);
+        let ptr = ArenaBox::as_ptr(&secret) as *const u8;
+
+        unsafe {
+            witness.secure_wipe();
+            assert!(std::slice::from_raw_parts(ptr, 32).iter().all(|&b| b == 0));
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "never securely wiped")]
+    fn test_unwiped_secret_panics_on_drop() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+        witness.alloc(64, 8);
+        drop(manager);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_taint_cleared_by_wipe() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        // Untainted allocations may be rewound freely...
+        let marker = witness.inner.checkpoint();
+        witness.inner.alloc(64, 8);
+        unsafe { witness.inner.restore(marker).unwrap() };
+
+        // ...but rewinding over a secret is a leak.
+        witness.alloc(64, 8);
+        let leak = std::panic::catch_unwind(|| unsafe { witness.inner.reset() });
+        let message = *leak.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("64 secret bytes at offset 0"));
+
+        unsafe { witness.secure_wipe() };
+        unsafe { witness.inner.reset() };
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, feature = "growable"))]
+    fn test_taint_follows_chained_chunks() {
+        let manager = ArenaManager::with_sizes(64 * 1024, 64 * 1024, 64 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+        witness.inner.set_growable(true);
+
+        // The secret cannot fit the region and lands in a chunk.
+        witness.inner.alloc(32 * 1024, 8);
+        let spilled = witness.alloc(64 * 1024, 8);
+        assert!(!spilled.is_null());
+        let (base, limit) = witness.inner.region();
+        assert!(!(base..limit).contains(&(spilled as usize)));
+
+        let leak = std::panic::catch_unwind(|| unsafe { witness.inner.reset() });
+        let message = *leak.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.contains("65536 secret bytes at offset 0 of a chained chunk"));
+
+        // Wiping clears the secrets in both the region and the chunk.
+        unsafe { witness.secure_wipe() };
+        unsafe { witness.inner.reset() };
+    }
+
+    #[test]
+    fn test_reserve_prezeroes_recycled_memory() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        // Leave old data behind without tainting it as a secret.
+        let first = witness.inner.alloc(8192, 8);
+        unsafe {
+            std::ptr::write_bytes(first, 0xAB, 8192);
+            witness.inner.reset();
+            witness.reserve(4096);
+        }
+        let alloc_zeroed = |size| {
+            let ptr = witness.alloc(size, 8);
+            unsafe { std::slice::from_raw_parts(ptr, size) }
+                .iter()
+                .all(|&b| b == 0)
+        };
+        // Inside the reserved prefix, zeroed by `reserve`...
+        assert!(alloc_zeroed(4096));
+        // ...and past it, zeroed on allocation as usual.
+        assert!(alloc_zeroed(4096));
+
+        // A rewind drops the reservation.
+        unsafe {
+            witness.secure_wipe();
+            std::ptr::write_bytes(witness.inner.alloc(8192, 8), 0xCD, 8192);
+            witness.inner.reset();
+        }
+        assert!(alloc_zeroed(4096));
+        unsafe { witness.secure_wipe() };
+    }
+
+    #[test]
+    fn test_alloc_zeroes_only_recycled_memory() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let fresh = witness.inner.alloc(4096, 8);
+        assert!(!witness.inner.is_recycled());
+        assert!(unsafe { std::slice::from_raw_parts(fresh, 4096) }
+            .iter()
+            .all(|&b| b == 0));
+        unsafe {
+            std::ptr::write_bytes(fresh, 0xAB, 4096);
+            // A plain reset leaves the data in place...
+            witness.inner.reset();
+        }
+
+        // ...so reallocating the same memory must zero it.
+        let reused = witness.alloc(4096, 8);
+        assert_eq!(reused, fresh);
+        assert!(witness.inner.is_recycled());
+        assert!(unsafe { std::slice::from_raw_parts(reused, 4096) }
+            .iter()
+            .all(|&b| b == 0));
+        unsafe { witness.secure_wipe() };
+    }
+
+    #[test]
+    fn test_alloc_zeroes_caller_buffer() {
+        let mut buffer = vec![0xFFu8; 1024];
+        let inner = unsafe { BumpAlloc::new(buffer.as_mut_ptr(), buffer.len()) };
+        let witness = WitnessArena::new(Arc::new(inner));
+        let ptr = witness.alloc(256, 8);
+        assert!(unsafe { std::slice::from_raw_parts(ptr, 256) }
+            .iter()
+            .all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_alloc_slice_zeroed_after_wipe() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let values = witness.alloc_slice_zeroed::<u64>(128).unwrap();
+        assert!(values.iter().all(|&v| v == 0));
+        values.fill(u64::MAX);
+
+        unsafe { witness.secure_wipe() };
+        let values = witness.alloc_slice_zeroed::<u64>(128).unwrap();
+        assert!(values.iter().all(|&v| v == 0));
+        unsafe { witness.secure_wipe() };
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_wipe_policy_applies_last_pattern() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness()).with_wipe_policy(WipePolicy {
+            passes: 3,
+            patterns: &[0x00, 0xFF, 0xAA],
+        });
+
+        let ptr = witness.alloc(4096, 8);
+        unsafe {
+            std::ptr::write_bytes(ptr, 0x11, 4096);
+            witness.secure_wipe();
+            let wiped = std::slice::from_raw_parts(ptr, 4096);
+            assert!(wiped.iter().all(|&b| b == 0xAA));
+        }
+        assert_eq!(witness.used(), 0);
+
+        // Recycled memory is still handed out zeroed.
+        let ptr = witness.alloc(4096, 8);
+        assert!(unsafe { std::slice::from_raw_parts(ptr, 4096) }
+            .iter()
+            .all(|&b| b == 0));
+        unsafe { witness.secure_wipe() };
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_multipass_wipe_ends_zeroed() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let ptr = witness.alloc(4099, 8);
+        unsafe {
+            std::ptr::write_bytes(ptr, 0x5C, 4099);
+
+            let mut rng_calls = 0;
+            witness.secure_wipe_multipass_with(4, || {
+                rng_calls += 1;
+                0xDEAD_BEEF_CAFE_F00D
+            });
+            assert_eq!(rng_calls, 4099usize.div_ceil(8));
+            assert!(std::slice::from_raw_parts(ptr, 4099)
+                .iter()
+                .all(|&b| b == 0));
+
+            std::ptr::write_bytes(witness.alloc(4096, 8), 0x5C, 4096);
+            witness.secure_wipe_multipass(3);
+            assert!(std::slice::from_raw_parts(ptr, 4096)
+                .iter()
+                .all(|&b| b == 0));
+        }
+        assert_eq!(witness.used(), 0);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
+    fn test_secure_wipe_verified() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        let ptr = witness.alloc(8192, 8);
+        unsafe {
+            std::ptr::write_bytes(ptr, 0xEE, 8192);
+            assert_eq!(witness.secure_wipe_verified(), Ok(()));
+            assert!(std::slice::from_raw_parts(ptr, 8192)
+                .iter()
+                .all(|&b| b == 0));
+        }
+        assert_eq!(witness.used(), 0);
+    }
+
+    #[test]
+    fn test_wipe_error_display() {
+        let err = WipeError { offset: 42 };
+        assert!(format!("{}", err).contains("42"));
+    }
+
     #[test]
     fn test_fresh_memory_not_double_zeroed() {
         let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
@@ -125,6 +760,7 @@ mod tests {
             for i in 0..1024 {
                 assert_eq!(*ptr.add(i), 0);
             }
+            witness.secure_wipe();
         }
     }
 
@@ -153,6 +789,7 @@ mod tests {
             for i in 0..1024 {
                 assert_eq!(*ptr2.add(i), 0);
             }
+            witness.secure_wipe();
         }
     }
 
@@ -169,6 +806,26 @@ mod tests {
             for i in 0..1024 {
                 assert_eq!(*ptr.add(i), 0);
             }
+            witness.secure_wipe();
+        }
+    }
+
+    #[test]
+    fn test_lock_pages() {
+        let manager = ArenaManager::with_sizes(1024 * 1024, 1024 * 1024, 1024 * 1024).unwrap();
+        let witness = WitnessArena::new(manager.witness());
+
+        match witness.lock_pages() {
+            Ok(()) => {
+                assert!(witness.is_locked());
+                witness.unlock_pages().unwrap();
+                assert!(!witness.is_locked());
+            }
+            // RLIMIT_MEMLOCK may be too small; the failure must be reported, not panic
+            Err(err) => {
+                assert_eq!(err.requested_size, 1024 * 1024);
+                assert!(!witness.is_locked());
+            }
         }
     }
 }
@@ -0,0 +1,25 @@
+//! Marker trait for types whose all-zero bit pattern is a valid value.
+//!
+//! The typed slice helpers on the arenas hand out zero-initialized memory
+//! as `&mut [T]`, which is only sound if zero bytes form a valid `T`.
+
+/// Types that can be safely created from all-zero bytes.
+///
+/// Implemented for the primitive integer and float types, `bool`, and
+/// arrays of them. Downstream crates can implement it for their own
+/// field-element types, e.g. a Montgomery-form `[u64; 4]` wrapper.
+///
+/// # Safety
+/// The all-zero bit pattern must be a valid value of the implementing type,
+/// and the type must not have drop glue.
+pub unsafe trait Zeroable: Copy {}
+
+macro_rules! impl_zeroable {
+    ($($ty:ty),* $(,)?) => {
+        $(unsafe impl Zeroable for $ty {})*
+    };
+}
+
+impl_zeroable!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool);
+
+unsafe impl<T: Zeroable, const N: usize> Zeroable for [T; N] {}
@@ -0,0 +1,28 @@
+//! Arena sizes from the environment.
+//!
+//! `std::env::set_var` races with the raw `getenv` behind
+//! `ArenaConfig::from_env`, so these checks run alone in their own test
+//! binary, as a single test.
+
+use zk_nalloc::{sys, ArenaConfig, POLY_ARENA_SIZE, SCRATCH_ARENA_SIZE};
+
+#[test]
+fn test_size_variables() {
+    std::env::set_var("NALLOC_WITNESS_SIZE", "3M");
+    std::env::set_var("NALLOC_POLY_SIZE", "lots");
+    std::env::remove_var("NALLOC_SCRATCH_SIZE");
+
+    let mut buf = [0u8; 16];
+    assert_eq!(
+        sys::env_var(c"NALLOC_WITNESS_SIZE", &mut buf),
+        Some(&b"3M"[..])
+    );
+    assert_eq!(sys::env_var(c"NALLOC_WITNESS_SIZE", &mut [0u8; 1]), None);
+    assert_eq!(sys::env_var(c"NALLOC_SCRATCH_SIZE", &mut buf), None);
+
+    // Invalid and unset sizes keep the defaults.
+    let config = ArenaConfig::from_env();
+    assert_eq!(config.witness_size, 3 * 1024 * 1024);
+    assert_eq!(config.poly_size, POLY_ARENA_SIZE);
+    assert_eq!(config.scratch_size, SCRATCH_ARENA_SIZE);
+}
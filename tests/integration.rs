@@ -25,6 +25,26 @@ fn test_determinism() {
     }
 }
 
+#[test]
+fn test_deterministic_mode_never_falls_back() {
+    let alloc = NAlloc::deterministic();
+    assert!(alloc.is_deterministic());
+    let layout = Layout::from_size_align(4096, 64).unwrap();
+
+    unsafe {
+        let base = alloc.alloc(layout) as usize;
+        for i in 1..4 {
+            assert_eq!(alloc.alloc(layout) as usize, base + i * 4096);
+        }
+
+        // Larger than the scratch arena: null rather than a system pointer.
+        let huge = Layout::from_size_align(zk_nalloc::SCRATCH_ARENA_SIZE * 2, 8).unwrap();
+        alloc.set_large_alloc_threshold(usize::MAX);
+        assert!(alloc.alloc(huge).is_null());
+        assert_eq!(alloc.stats().system_fallbacks, 0);
+    }
+}
+
 #[test]
 fn test_deterministic_layout_after_reset() {
     let alloc = NAlloc::new();
@@ -58,6 +78,7 @@ fn test_deterministic_layout_after_reset() {
 // ============================================================================
 
 #[test]
+#[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
 fn test_witness_security() {
     let alloc = NAlloc::new();
     let witness = alloc.witness();
@@ -87,6 +108,7 @@ fn test_witness_security() {
 }
 
 #[test]
+#[cfg_attr(feature = "sanitizer", ignore = "reads freed arena memory")]
 fn test_witness_large_secure_wipe() {
     let alloc = NAlloc::new();
     let witness = alloc.witness();
@@ -283,6 +305,7 @@ fn test_concurrent_witness_access() {
 }
 
 #[test]
+#[allow(clippy::manual_is_multiple_of)]
 fn test_concurrent_polynomial_fft_alignment() {
     let alloc = Arc::new(NAlloc::new());
     let alignment_errors = Arc::new(AtomicUsize::new(0));